@@ -1,3 +1,4 @@
+// Keys allowed to bootstrap the admin registry, every other privileged instruction reads the registry
 #[cfg(feature = "localhost")]
 pub const ROOT_KEYS: &[&str] = &[
   "8ST8fTBGKaVPx4f1KG1zMMw4EJmSJBW2UgX1JR2pPoVa",
@@ -17,15 +18,36 @@ pub const ROOT_KEYS: &[&str] = &[
   "GnzQDYm2gvwZ8wRVmuwVAeHx5T44ovC735vDgSNhumzQ",
 ];
 
+// Filled with the bootstrap key when building a release, see Upgrading in README
 #[cfg(all(not(feature = "localhost"), not(feature = "devnet"), not(feature = "unit-test")))]
 pub const ROOT_KEYS: &[&str] = &[
   ""
 ];
 
+pub const ADMIN_REGISTRY_SEED_1: &[u8] = &[241, 130, 106, 126, 178, 232, 140, 4];
+pub const ADMIN_REGISTRY_SEED_2: &[u8] = &[3, 50, 193, 153, 232, 161, 192, 14];
 pub const APP_DATA_SEED_1: &[u8] = &[144, 146, 13, 147, 226, 199, 230, 50];
 pub const APP_DATA_SEED_2: &[u8] = &[15, 81, 173, 106, 105, 203, 253, 99];
 pub const CUSD_PRECISION: u64 = 1000000; // decimals = 6
+pub const FEE_SIGNER_SEED_1: &[u8] = &[91, 226, 17, 140, 63, 208, 121, 5];
+pub const FEE_SIGNER_SEED_2: &[u8] = &[174, 38, 250, 99, 12, 187, 46, 233];
 pub const ROOT_SIGNER_SEED_1: &[u8] = &[2, 151, 229, 53, 244, 77, 229, 7];
 pub const ROOT_SIGNER_SEED_2: &[u8] = &[68, 203, 0, 94, 226, 230, 93, 156];
+pub const TIMELOCK_OPERATION_SEED: &[u8] = &[77, 18, 200, 31, 165, 9, 244, 130];
+pub const TWAP_SEED: &[u8] = &[163, 87, 12, 210, 54, 199, 33, 118];
 
+pub const DEFAULT_MAX_PRICE_AGE: u32 = 3600; // seconds
+
+// Kept short on localhost so the client tests can execute the operations they queue
+#[cfg(feature = "localhost")]
+pub const DEFAULT_TIMELOCK_DELAY: u32 = 10; // seconds
+
+#[cfg(not(feature = "localhost"))]
+pub const DEFAULT_TIMELOCK_DELAY: u32 = 86400; // seconds
+
+pub const MAX_ADMIN_COUNT: usize = 10;
+pub const MAX_EXTRA_PRICE_FEED_COUNT: usize = 2;
+pub const MAX_INPUT_TOKEN_COUNT: usize = 8;
+pub const MAX_ROLE_MEMBER_COUNT: usize = 10;
 pub const SYSTEM_FEE_CAP: u16 = 2000;
+pub const TWAP_EXPONENT: i32 = -12; // TWAP prices are stored with 12 decimals
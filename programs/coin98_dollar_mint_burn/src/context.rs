@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
 use crate::constant::{
+  ADMIN_REGISTRY_SEED_1,
+  ADMIN_REGISTRY_SEED_2,
   APP_DATA_SEED_1,
   APP_DATA_SEED_2,
+  FEE_SIGNER_SEED_1,
+  FEE_SIGNER_SEED_2,
+  MAX_INPUT_TOKEN_COUNT,
   ROOT_SIGNER_SEED_1,
   ROOT_SIGNER_SEED_2,
+  TIMELOCK_OPERATION_SEED,
+  TWAP_SEED,
 };
 use crate::error::{
   ErrorCode,
 };
 use crate::state::{
+  AdminRegistry,
   AppData,
   Burner,
   Minter,
+  TimelockOperation,
+  Twap,
 };
 use crate::external::anchor_spl_token::{
   TokenAccount,
@@ -31,10 +41,19 @@ use crate::external::spl_token::{
 #[instruction(derivation_path: Vec<u8>)]
 pub struct CreateMinterContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: config manager, verified using #access_control
   #[account(signer, mut)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(
     init,
     seeds = [
@@ -43,7 +62,7 @@ pub struct CreateMinterContext<'info> {
     ],
     bump,
     payer = root,
-    space = 16 + Minter::size(8),
+    space = 16 + Minter::size(MAX_INPUT_TOKEN_COUNT as u8),
   )]
   pub minter: Account<'info, Minter>,
 
@@ -53,10 +72,39 @@ pub struct CreateMinterContext<'info> {
 #[derive(Accounts)]
 pub struct SetMinterContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: config manager, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(mut)]
+  pub minter: Account<'info, Minter>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateMinterContext<'info> {
+
+  /// CHECK: pauser, verified using #access_control
   #[account(signer)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(mut)]
   pub minter: Account<'info, Minter>,
 }
@@ -65,10 +113,19 @@ pub struct SetMinterContext<'info> {
 #[instruction(derivation_path: Vec<u8>)]
 pub struct CreateBurnerContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: config manager, verified using #access_control
   #[account(signer, mut)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(
     init,
     seeds = [
@@ -87,10 +144,39 @@ pub struct CreateBurnerContext<'info> {
 #[derive(Accounts)]
 pub struct SetBurnerContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: config manager, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(mut)]
+  pub burner: Account<'info, Burner>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateBurnerContext<'info> {
+
+  /// CHECK: pauser, verified using #access_control
   #[account(signer)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(mut)]
   pub burner: Account<'info, Burner>,
 }
@@ -215,10 +301,19 @@ pub struct BurnContext<'info> {
 #[derive(Accounts)]
 pub struct WithdrawTokenContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: treasurer, verified using #access_control
   #[account(signer)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(
     seeds = [
       APP_DATA_SEED_1,
@@ -257,10 +352,19 @@ pub struct WithdrawTokenContext<'info> {
 #[derive(Accounts)]
 pub struct UnlockTokenMintContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: mint authority custodian, verified using #access_control
   #[account(signer)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(
     seeds = [
       APP_DATA_SEED_1,
@@ -297,10 +401,19 @@ pub struct UnlockTokenMintContext<'info> {
 #[derive(Accounts)]
 pub struct CreateAppDataContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: program admin, verified using #access_control
   #[account(signer, mut)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(
     init,
     seeds = [
@@ -316,13 +429,174 @@ pub struct CreateAppDataContext<'info> {
   pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CreateAdminRegistryContext<'info> {
+
+  /// CHECK: bootstrap key listed in ROOT_KEYS, verified using #access_control
+  #[account(signer, mut)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    init,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump,
+    payer = root,
+    space = 16 + AdminRegistry::LEN,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAppDataContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer, mut)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  /// CHECK: AppData in its legacy layout, verified in instruction
+  #[account(
+    mut,
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump,
+  )]
+  pub app_data: AccountInfo<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMinterContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer, mut)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  /// CHECK: Minter in its legacy layout, verified in instruction
+  #[account(mut)]
+  pub minter: AccountInfo<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBurnerContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer, mut)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  /// CHECK: Burner in its legacy layout, verified in instruction
+  #[account(mut)]
+  pub burner: AccountInfo<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct SetAppDataContext<'info> {
 
-  /// CHECK: program owner, verified using #access_control
+  /// CHECK: config manager, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(
+    mut,
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+}
+
+#[derive(Accounts)]
+pub struct PauseContext<'info> {
+
+  /// CHECK: pauser, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(
+    mut,
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
   #[account(signer)]
   pub root: AccountInfo<'info>,
 
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
   #[account(
     mut,
     seeds = [
@@ -333,3 +607,464 @@ pub struct SetAppDataContext<'info> {
   )]
   pub app_data: Account<'info, AppData>,
 }
+
+#[derive(Accounts)]
+pub struct AddAdminContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAdminContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminContext<'info> {
+
+  /// CHECK: pending admin, verified in instruction
+  #[account(signer)]
+  pub pending_admin: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminTransferContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct GrantRoleContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRoleContext<'info> {
+
+  /// CHECK: program admin, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+}
+
+#[derive(Accounts)]
+#[instruction(derivation_path: Vec<u8>)]
+pub struct QueueOperationContext<'info> {
+
+  /// CHECK: role holder required by the operation, verified using #access_control
+  #[account(signer, mut)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  #[account(
+    init,
+    seeds = [
+      TIMELOCK_OPERATION_SEED,
+      &*derivation_path,
+    ],
+    bump,
+    payer = root,
+    space = 16 + TimelockOperation::LEN,
+  )]
+  pub timelock_operation: Account<'info, TimelockOperation>,
+
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveOperationContext<'info> {
+
+  /// CHECK: multisig signer, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(mut)]
+  pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOperationContext<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(
+    mut,
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  /// CHECK: PDA as root authority of the program
+  #[account(
+    seeds = [
+      ROOT_SIGNER_SEED_1,
+      ROOT_SIGNER_SEED_2,
+    ],
+    bump = app_data.signer_nonce,
+  )]
+  pub root_signer: AccountInfo<'info>,
+
+  #[account(mut)]
+  pub timelock_operation: Account<'info, TimelockOperation>,
+
+  /// CHECK: Solana native Token Program
+  #[account(
+    constraint = is_token_program(&token_program) @ErrorCode::InvalidAccount,
+  )]
+  pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOperationContext<'info> {
+
+  /// CHECK: pauser, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(mut)]
+  pub timelock_operation: Account<'info, TimelockOperation>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTwapContext<'info> {
+
+  /// CHECK: config manager, verified using #access_control
+  #[account(signer, mut)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  /// CHECK: price feed tracked by the TWAP
+  pub price_feed: AccountInfo<'info>,
+
+  #[account(
+    init,
+    seeds = [
+      TWAP_SEED,
+      price_feed.key().as_ref(),
+    ],
+    bump,
+    payer = root,
+    space = 16 + Twap::LEN,
+  )]
+  pub twap: Account<'info, Twap>,
+
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PokeContext<'info> {
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  #[account(
+    mut,
+    seeds = [
+      TWAP_SEED,
+      price_feed.key().as_ref(),
+    ],
+    bump = twap.nonce,
+  )]
+  pub twap: Account<'info, Twap>,
+
+  /// CHECK: price feed tracked by the TWAP, verified using seeds of twap
+  pub price_feed: AccountInfo<'info>,
+
+  /// CHECK: Chainlink program
+  #[account(
+    constraint = is_chainlink_program(&chainlink_program) @ErrorCode::InvalidAccount,
+  )]
+  pub chainlink_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CollectFeesContext<'info> {
+
+  /// CHECK: treasurer, verified using #access_control
+  #[account(signer)]
+  pub root: AccountInfo<'info>,
+
+  #[account(
+    seeds = [
+      ADMIN_REGISTRY_SEED_1,
+      ADMIN_REGISTRY_SEED_2,
+    ],
+    bump = admin_registry.nonce,
+  )]
+  pub admin_registry: Account<'info, AdminRegistry>,
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  /// CHECK: PDA as root authority of the program
+  #[account(
+    seeds = [
+      ROOT_SIGNER_SEED_1,
+      ROOT_SIGNER_SEED_2,
+    ],
+    bump = app_data.signer_nonce,
+  )]
+  pub root_signer: AccountInfo<'info>,
+
+  /// CHECK: PDA as owner of the token accounts holding burn fees
+  #[account(
+    seeds = [
+      FEE_SIGNER_SEED_1,
+      FEE_SIGNER_SEED_2,
+    ],
+    bump = app_data.fee_signer_nonce,
+  )]
+  pub fee_signer: AccountInfo<'info>,
+
+  /// CHECK: CUSD Token Mint
+  #[account(
+    mut,
+    constraint = is_cusd_token_mint(&cusd_mint) @ErrorCode::InvalidAccount,
+  )]
+  pub cusd_mint: AccountInfo<'info>,
+
+  /// CHECK: Solana native Token Program
+  #[account(
+    constraint = is_token_program(&token_program) @ErrorCode::InvalidAccount,
+  )]
+  pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteMintContext<'info> {
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  pub minter: Account<'info, Minter>,
+
+  /// CHECK: Chainlink program
+  #[account(
+    constraint = is_chainlink_program(&chainlink_program) @ErrorCode::InvalidAccount,
+  )]
+  pub chainlink_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteBurnContext<'info> {
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  pub burner: Account<'info, Burner>,
+
+  /// CHECK: Chainlink program
+  #[account(
+    constraint = is_chainlink_program(&chainlink_program) @ErrorCode::InvalidAccount,
+  )]
+  pub chainlink_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TripMinterCircuitBreakerContext<'info> {
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  #[account(mut)]
+  pub minter: Account<'info, Minter>,
+
+  /// CHECK: Chainlink program
+  #[account(
+    constraint = is_chainlink_program(&chainlink_program) @ErrorCode::InvalidAccount,
+  )]
+  pub chainlink_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TripBurnerCircuitBreakerContext<'info> {
+
+  #[account(
+    seeds = [
+      APP_DATA_SEED_1,
+      APP_DATA_SEED_2,
+    ],
+    bump = app_data.nonce,
+  )]
+  pub app_data: Account<'info, AppData>,
+
+  #[account(mut)]
+  pub burner: Account<'info, Burner>,
+
+  /// CHECK: Chainlink program
+  #[account(
+    constraint = is_chainlink_program(&chainlink_program) @ErrorCode::InvalidAccount,
+  )]
+  pub chainlink_program: AccountInfo<'info>,
+}
@@ -17,4 +17,67 @@ pub enum ErrorCode {
 
   #[msg(CUSD Factory: Factory unavailable)]
   Unavailable,
+
+  #[msg("CUSD Factory: Operation already executed or cancelled")]
+  OperationFinalized,
+
+  #[msg("CUSD Factory: Timelock not expired")]
+  TimelockNotExpired,
+
+  #[msg("CUSD Factory: Change must be queued in timelock")]
+  TimelockRequired,
+
+  #[msg("CUSD Factory: Approval threshold not met")]
+  ThresholdNotMet,
+
+  #[msg("CUSD Factory: Paused")]
+  Paused,
+
+  #[msg("CUSD Factory: Input amount exceeds maximum")]
+  MaxInputAmountExceeded,
+
+  #[msg("CUSD Factory: CUSD amount exceeds maximum")]
+  MaxCusdInExceeded,
+
+  #[msg("CUSD Factory: Output amount below minimum")]
+  MinAmountOutNotMet,
+
+  #[msg("CUSD Factory: Stale price")]
+  StalePrice,
+
+  #[msg("CUSD Factory: Invalid price")]
+  InvalidPrice,
+
+  #[msg("CUSD Factory: Price out of bounds")]
+  PriceOutOfBounds,
+
+  #[msg("CUSD Factory: Price sources diverge")]
+  PriceDivergence,
+
+  #[msg("CUSD Factory: Not enough fresh price feeds")]
+  QuorumNotMet,
+
+  #[msg("CUSD Factory: Price deviates from last accepted price")]
+  PriceDeviation,
+
+  #[msg("CUSD Factory: Collateral is off peg")]
+  Depegged,
+
+  #[msg("CUSD Factory: Price confidence too wide")]
+  ConfidenceTooWide,
+
+  #[msg("CUSD Factory: Clock unavailable")]
+  ClockUnavailable,
+
+  #[msg("CUSD Factory: CPI failed.")]
+  CpiFailed,
+
+  #[msg("CUSD Factory: Invalid token account")]
+  InvalidTokenAccount,
+
+  #[msg("CUSD Factory: Math overflow")]
+  MathOverflow,
+
+  #[msg("CUSD Factory: Circuit breaker tripped, price deviates from last accepted price")]
+  CircuitBreakerTripped,
 }
@@ -5,6 +5,50 @@ use anchor_lang::prelude::{
   event,
   Pubkey,
 };
+use crate::state::{
+  Operation,
+  PriceConfig,
+  Role,
+};
+
+#[event]
+pub struct AddAdminEvent {
+  pub admin: Pubkey,
+}
+
+#[event]
+pub struct RemoveAdminEvent {
+  pub admin: Pubkey,
+}
+
+#[event]
+pub struct ProposeAdminEvent {
+  pub proposer: Pubkey,
+  pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AcceptAdminEvent {
+  pub previous_admin: Pubkey,
+  pub admin: Pubkey,
+}
+
+#[event]
+pub struct CancelAdminTransferEvent {
+  pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct GrantRoleEvent {
+  pub role: Role,
+  pub member: Pubkey,
+}
+
+#[event]
+pub struct RevokeRoleEvent {
+  pub role: Role,
+  pub member: Pubkey,
+}
 
 #[event]
 pub struct CreateMinterEvent {
@@ -18,12 +62,18 @@ pub struct SetMinterEvent {
   pub input_decimals: Vec<u16>,
   pub input_percentages: Vec<u16>,
   pub input_price_feeds: Vec<Pubkey>,
+  pub input_price_configs: Vec<PriceConfig>,
   pub fee_percent: u16,
   pub total_minted_limit: u64,
   pub per_period_minted_limit: u64,
   pub min_amount: u64,
 }
 
+#[event]
+pub struct DeactivateMinterEvent {
+  pub minter: Pubkey,
+}
+
 #[event]
 pub struct CreateBurnerEvent {
   pub is_active: bool,
@@ -35,15 +85,115 @@ pub struct SetBurnerEvent {
   pub output_token: Pubkey,
   pub output_decimals: u16,
   pub output_price_feed: Pubkey,
+  pub output_price_config: PriceConfig,
   pub fee_percent: u16,
   pub total_burned_limit: u64,
   pub per_period_burned_limit: u64,
   pub min_amount: u64,
 }
 
+#[event]
+pub struct DeactivateBurnerEvent {
+  pub burner: Pubkey,
+}
+
+#[event]
+pub struct CreateTwapEvent {
+  pub twap: Pubkey,
+  pub price_feed: Pubkey,
+  pub window: u32,
+  pub price_config: PriceConfig,
+}
+
+// amount is the CUSD value of the inputs and includes the fee, prices are the ones applied
+#[event]
+pub struct MintEvent {
+  pub user: Pubkey,
+  pub minter: Pubkey,
+  pub recipient: Pubkey,
+  pub amount: u64,
+  pub fee: u64,
+  pub input_amounts: Vec<u64>,
+  pub input_prices: Vec<u64>,
+  pub input_precisions: Vec<u64>,
+  pub total_minted_amount: u64,
+  pub per_period_minted_amount: u64,
+  pub last_period_timestamp: i64,
+}
+
+// output_amount includes the fee, which is denominated in output token
+#[event]
+pub struct BurnEvent {
+  pub user: Pubkey,
+  pub burner: Pubkey,
+  pub recipient: Pubkey,
+  pub cusd_amount: u64,
+  pub output_amount: u64,
+  pub output_price: u64,
+  pub output_precision: u64,
+  pub fee: u64,
+  pub total_burned_amount: u64,
+  pub per_period_burned_amount: u64,
+  pub last_period_timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTrippedEvent {
+  pub authority: Pubkey,
+  pub token: Pubkey,
+  pub last_price: u64,
+  pub price: u64,
+  pub deviation: u64,
+  pub is_paused: bool,
+}
+
 #[event]
 pub struct SetAppDataEvent {
   pub limit: u32,
+  pub timelock_delay: u32,
+  pub max_price_age: u32,
+}
+
+#[event]
+pub struct SetPauseEvent {
+  pub is_paused: bool,
+  pub is_mint_paused: bool,
+  pub is_burn_paused: bool,
+}
+
+#[event]
+pub struct QueueOperationEvent {
+  pub timelock_operation: Pubkey,
+  pub proposer: Pubkey,
+  pub operation: Operation,
+  pub eta: i64,
+}
+
+#[event]
+pub struct ApproveOperationEvent {
+  pub timelock_operation: Pubkey,
+  pub approver: Pubkey,
+  pub approval_count: u8,
+}
+
+#[event]
+pub struct ExecuteOperationEvent {
+  pub timelock_operation: Pubkey,
+  pub operation: Operation,
+}
+
+#[event]
+pub struct CancelOperationEvent {
+  pub timelock_operation: Pubkey,
+  pub operation: Operation,
+}
+
+#[event]
+pub struct CollectFeesEvent {
+  pub fee_account: Pubkey,
+  pub token: Pubkey,
+  pub recipient_token_account: Pubkey,
+  pub amount: u64,
 }
 
 #[event]
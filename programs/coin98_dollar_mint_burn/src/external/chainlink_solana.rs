@@ -1,3 +1,6 @@
+use anchor_lang::prelude::{
+  Result,
+};
 use chainlink_solana::{
   decimals,
   latest_round_data,
@@ -12,6 +15,17 @@ use solana_program::{
 use std::convert::{
   TryFrom,
 };
+use crate::error::{
+  ErrorCode,
+};
+use crate::external::oracle::{
+  MIN_EXPONENT,
+  OracleAdapter,
+  OraclePrice,
+};
+use crate::state::{
+  OracleType,
+};
 
 #[cfg(feature = "localhost")]
 declare_id!("DFeedTiF3G7eojEqc7KuqJFbBD3idV9y7i6Q7LxKtF7e");
@@ -25,23 +39,48 @@ declare_id!("HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJWHny");
 pub fn get_price_feed<'i>(
   chainlink_program: &AccountInfo<'i>,
   feed_account: &AccountInfo<'i>,
-) -> (u64, u64) {
+) -> Result<OraclePrice> {
 
   let round = latest_round_data(
       chainlink_program.clone(),
       feed_account.clone(),
-    ).unwrap();
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
   let precision = decimals(
       chainlink_program.clone(),
       feed_account.clone(),
-    ).unwrap();
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
+  if -i32::from(precision) < MIN_EXPONENT {
+    return Err(ErrorCode::InvalidPrice.into());
+  }
 
-  let price = u64::try_from(round.answer).unwrap();
-  let precision = u64::pow(10, u32::from(precision));
+  // zero, negative and out of range answers are all rejected
+  let price = u64::try_from(round.answer).unwrap_or(0);
+  if price == 0 {
+    return Err(ErrorCode::InvalidPrice.into());
+  }
 
-  msg!("Price fetched: {}/{} at {} in block {}", price, precision, round.timestamp, round.slot);
+  msg!("Price fetched: {}/{} at {} in block {}", price, u64::pow(10, u32::from(precision)), round.timestamp, round.slot);
+
+  Ok(OraclePrice {
+    value: price,
+    exponent: -i32::from(precision),
+    timestamp: i64::from(round.timestamp),
+    confidence: 0,
+    source: OracleType::Chainlink,
+  })
+}
+
+pub struct ChainlinkOracle<'a, 'info> {
+  pub program: &'a AccountInfo<'info>,
+  pub feed: &'a AccountInfo<'info>,
+}
 
-  (price, precision)
+impl<'a, 'info> OracleAdapter for ChainlinkOracle<'a, 'info> {
+  fn get_price(&self) -> Result<OraclePrice> {
+    get_price_feed(self.program, self.feed)
+  }
 }
 
 pub fn is_chainlink_program<'a>(account: &AccountInfo<'a>) -> bool {
@@ -1,5 +1,7 @@
 pub mod anchor_spl_token;
 pub mod chainlink_solana;
 pub mod cusd_token_mint;
+pub mod oracle;
+pub mod pyth;
 pub mod spl_associated_token;
 pub mod spl_token;
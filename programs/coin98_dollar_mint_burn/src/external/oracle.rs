@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use crate::error::{
+  ErrorCode,
+};
+use crate::external::chainlink_solana::{
+  ChainlinkOracle,
+};
+use crate::external::pyth::{
+  PythOracle,
+};
+use crate::state::{
+  OracleType,
+};
+
+// Finest exponent an adapter may return, keeps the precision within u64
+pub const MIN_EXPONENT: i32 = -18;
+
+// Price normalized across oracle sources, the real price is value * 10^exponent and confidence uses the same exponent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OraclePrice {
+  pub value: u64,
+  pub exponent: i32,
+  pub timestamp: i64,
+  pub confidence: u64,
+  pub source: OracleType,
+}
+
+impl OraclePrice {
+  // Denominator of the price, adapters always normalize to an exponent between MIN_EXPONENT and zero
+  pub fn precision(&self) -> u64 {
+    u64::pow(10, self.exponent.unsigned_abs())
+  }
+
+  // Price minus confidence, the conservative value of a collateral paid in
+  pub fn min_value(&self) -> u64 {
+    self.value.saturating_sub(self.confidence)
+  }
+
+  // Price plus confidence, the conservative value of a token paid out
+  pub fn max_value(&self) -> Result<u64> {
+    let value = self.value.checked_add(self.confidence).ok_or(ErrorCode::MathOverflow)?;
+    Ok(value)
+  }
+
+  // Express the price with another exponent, digits are truncated when it is coarser
+  pub fn rescale(&self, exponent: i32) -> Result<OraclePrice> {
+    let scale = self.exponent.checked_sub(exponent)
+      .and_then(|difference| u64::checked_pow(10, difference.unsigned_abs()))
+      .ok_or(ErrorCode::MathOverflow)?;
+    let (value, confidence) = if exponent <= self.exponent {
+      (
+        self.value.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?,
+        self.confidence.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?,
+      )
+    }
+    else {
+      (self.value / scale, self.confidence / scale)
+    };
+
+    Ok(OraclePrice {
+      value,
+      exponent,
+      timestamp: self.timestamp,
+      confidence,
+      source: self.source,
+    })
+  }
+}
+
+pub trait OracleAdapter {
+  fn get_price(&self) -> Result<OraclePrice>;
+}
+
+// 1 USD per token, used for stable collaterals with no feed attached
+pub struct FixedOracle;
+
+impl OracleAdapter for FixedOracle {
+  fn get_price(&self) -> Result<OraclePrice> {
+    let clock = Clock::get().map_err(|_| ErrorCode::ClockUnavailable)?;
+    msg!("Price fetched: {}/{} at {} in block {}", 1, 1, clock.unix_timestamp, clock.slot);
+
+    Ok(OraclePrice {
+      value: 1,
+      exponent: 0,
+      timestamp: clock.unix_timestamp,
+      confidence: 0,
+      source: OracleType::Fixed,
+    })
+  }
+}
+
+pub fn get_oracle_price<'a, 'info>(
+  oracle_type: OracleType,
+  chainlink_program: &'a AccountInfo<'info>,
+  price_feed: &'a AccountInfo<'info>,
+) -> Result<OraclePrice> {
+
+  match oracle_type {
+    OracleType::Fixed => FixedOracle.get_price(),
+    OracleType::Chainlink => ChainlinkOracle {
+      program: chainlink_program,
+      feed: price_feed,
+    }.get_price(),
+    OracleType::Pyth => PythOracle {
+      feed: price_feed,
+    }.get_price(),
+  }
+}
+
+// Median of the prices rescaled to the finest exponent, the oldest timestamp and widest confidence are kept
+pub fn median_price(prices: &[OraclePrice]) -> Result<OraclePrice> {
+  let exponent = prices.iter().map(|price| price.exponent).min().ok_or(ErrorCode::InvalidPrice)?;
+  let rescaled_prices = prices.iter()
+    .map(|price| price.rescale(exponent))
+    .collect::<Result<Vec<OraclePrice>>>()?;
+  let mut values: Vec<u64> = rescaled_prices.iter()
+    .map(|price| price.value)
+    .collect();
+  values.sort_unstable();
+
+  let middle = values.len() / 2;
+  let value = if values.len() % 2 == 0 {
+    values[middle - 1] + (values[middle] - values[middle - 1]) / 2
+  }
+  else {
+    values[middle]
+  };
+
+  Ok(OraclePrice {
+    value,
+    exponent,
+    timestamp: rescaled_prices.iter().map(|price| price.timestamp).min().unwrap_or_default(),
+    confidence: rescaled_prices.iter().map(|price| price.confidence).max().unwrap_or_default(),
+    source: prices[0].source,
+  })
+}
+
+// Spread between the highest and lowest price in basis points of the lowest
+pub fn price_divergence(prices: &[OraclePrice]) -> Result<u64> {
+  let exponent = prices.iter().map(|price| price.exponent).min().ok_or(ErrorCode::InvalidPrice)?;
+  let values = prices.iter()
+    .map(|price| price.rescale(exponent).map(|price| price.value))
+    .collect::<Result<Vec<u64>>>()?;
+  let highest = values.iter().copied().max().unwrap_or_default();
+  let lowest = values.iter().copied().min().unwrap_or_default();
+  if lowest == 0 {
+    return Err(ErrorCode::InvalidPrice.into());
+  }
+
+  let divergence = u128::from(highest - lowest) * 10000 / u128::from(lowest);
+  Ok(u64::try_from(divergence).unwrap_or(u64::MAX))
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::{
+  Result,
+};
+use arrayref::{
+  array_ref,
+};
+use solana_program::{
+  account_info::{
+    AccountInfo,
+  },
+  declare_id,
+  msg,
+};
+use std::convert::{
+  TryFrom,
+};
+use crate::error::{
+  ErrorCode,
+};
+use crate::external::oracle::{
+  MIN_EXPONENT,
+  OracleAdapter,
+  OraclePrice,
+};
+use crate::state::{
+  OracleType,
+};
+
+#[cfg(feature = "localhost")]
+declare_id!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+
+#[cfg(feature = "devnet")]
+declare_id!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+
+#[cfg(feature = "unit-test")]
+declare_id!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+
+#[cfg(all(not(feature = "localhost"), not(feature = "devnet"), not(feature = "unit-test")))]
+declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+pub const MAGIC: u32 = 0xa1b2c3d4;
+pub const VERSION: u32 = 2;
+pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const PRICE_STATUS_TRADING: u32 = 1;
+pub const PRICE_ACCOUNT_LEN: usize = 240;
+
+// Aggregate price of a Pyth v2 price account
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PythPrice {
+  pub price: i64,
+  pub conf: u64,
+  pub expo: i32,
+  pub status: u32,
+  pub timestamp: i64,
+  pub slot: u64,
+}
+
+impl PythPrice {
+  pub fn unpack_from_slice(src: &[u8]) -> Result<Self> {
+    if src.len() < PRICE_ACCOUNT_LEN {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+    let magic = u32::from_le_bytes(*array_ref![src, 0, 4]);
+    let version = u32::from_le_bytes(*array_ref![src, 4, 4]);
+    let account_type = u32::from_le_bytes(*array_ref![src, 8, 4]);
+    if magic != MAGIC || version != VERSION || account_type != ACCOUNT_TYPE_PRICE {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+
+    Ok(PythPrice {
+      expo: i32::from_le_bytes(*array_ref![src, 20, 4]),
+      timestamp: i64::from_le_bytes(*array_ref![src, 96, 8]),
+      price: i64::from_le_bytes(*array_ref![src, 208, 8]),
+      conf: u64::from_le_bytes(*array_ref![src, 216, 8]),
+      status: u32::from_le_bytes(*array_ref![src, 224, 4]),
+      slot: u64::from_le_bytes(*array_ref![src, 232, 8]),
+    })
+  }
+}
+
+pub fn get_price_feed<'i>(
+  feed_account: &AccountInfo<'i>,
+) -> Result<OraclePrice> {
+
+  if *feed_account.owner != ID {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+  let data = feed_account.try_borrow_data().map_err(|_| ErrorCode::InvalidAccount)?;
+  let pyth_price = PythPrice::unpack_from_slice(&data)?;
+  if pyth_price.status != PRICE_STATUS_TRADING {
+    return Err(ErrorCode::InvalidPrice.into());
+  }
+
+  let price = u64::try_from(pyth_price.price).unwrap_or(0);
+  if price == 0 {
+    return Err(ErrorCode::InvalidPrice.into());
+  }
+  // positive exponents are folded into the value so the exponent is never above zero
+  let (price, conf, expo) = if pyth_price.expo < 0 {
+    (price, pyth_price.conf, pyth_price.expo)
+  }
+  else {
+    let scale = u64::checked_pow(10, pyth_price.expo.unsigned_abs()).ok_or(ErrorCode::MathOverflow)?;
+    (
+      price.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?,
+      pyth_price.conf.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?,
+      0i32,
+    )
+  };
+  if expo < MIN_EXPONENT {
+    return Err(ErrorCode::InvalidPrice.into());
+  }
+
+  msg!("Price fetched: {}/{} ±{} at {} in block {}", price, u64::pow(10, expo.unsigned_abs()), conf, pyth_price.timestamp, pyth_price.slot);
+
+  Ok(OraclePrice {
+    value: price,
+    exponent: expo,
+    timestamp: pyth_price.timestamp,
+    confidence: conf,
+    source: OracleType::Pyth,
+  })
+}
+
+pub struct PythOracle<'a, 'info> {
+  pub feed: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> OracleAdapter for PythOracle<'a, 'info> {
+  fn get_price(&self) -> Result<OraclePrice> {
+    get_price_feed(self.feed)
+  }
+}
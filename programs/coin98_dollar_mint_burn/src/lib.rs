@@ -2,39 +2,90 @@ pub mod constant;
 pub mod context;
 pub mod error;
 pub mod event;
+pub mod return_data;
 pub mod state;
 pub mod external;
+pub mod math;
 
 use anchor_lang::prelude::*;
 use solana_program::{
+  program::{
+    invoke,
+    set_return_data,
+  },
   program_pack::{
     Pack,
   },
+  system_instruction,
   system_program,
 };
 use crate::constant::{
   CUSD_PRECISION,
+  DEFAULT_MAX_PRICE_AGE,
+  DEFAULT_TIMELOCK_DELAY,
+  FEE_SIGNER_SEED_1,
+  FEE_SIGNER_SEED_2,
+  MAX_ADMIN_COUNT,
+  MAX_EXTRA_PRICE_FEED_COUNT,
+  MAX_INPUT_TOKEN_COUNT,
+  MAX_ROLE_MEMBER_COUNT,
   ROOT_KEYS,
   ROOT_SIGNER_SEED_1,
   ROOT_SIGNER_SEED_2,
   SYSTEM_FEE_CAP,
+  TWAP_EXPONENT,
 };
 use crate::context::*;
 use crate::error::{
   ErrorCode,
 };
 use crate::event::*;
+use crate::return_data::{
+  BurnResult,
+  MintResult,
+};
+use crate::state::{
+  AcceptedPrice,
+  AdminRegistry,
+  AppData,
+  Burner,
+  BurnerConfig,
+  LegacyAppData,
+  LegacyBurner,
+  LegacyMinter,
+  Minter,
+  MinterConfig,
+  Operation,
+  OracleType,
+  PriceConfig,
+  PriceMode,
+  Role,
+  Twap,
+};
 use crate::external::anchor_spl_token::{
   burn_token,
   mint_token,
   transfer_authority,
   transfer_token,
 };
-use crate::external::chainlink_solana::{
-  get_price_feed,
+use crate::external::oracle::{
+  get_oracle_price,
+  median_price,
+  price_divergence,
+  OraclePrice,
 };
 use crate::external::spl_token::{
+  self,
   TokenAccount,
+  TokenMint,
+};
+use crate::math::{
+  checked_add,
+  checked_mul_div,
+  checked_pow10,
+  checked_sub,
+  period_end,
+  Rounding,
 };
 
 #[cfg(feature = "localhost")]
@@ -53,7 +104,7 @@ declare_id!("CDMBw8drd8Ypct1YDTyJiUHN3KqP9tqtJJxjym5p3muQ");
 pub mod coin98_dollar_mint_burn {
   use super::*;
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::ConfigManager, *ctx.accounts.root.key))]
   pub fn create_minter(
     ctx: Context<CreateMinterContext>,
     _derivation_path: Vec<u8>,
@@ -66,6 +117,8 @@ pub mod coin98_dollar_mint_burn {
     minter.input_decimals = Vec::new();
     minter.input_percentages = Vec::new();
     minter.input_price_feeds = Vec::new();
+    minter.input_price_configs = Vec::new();
+    minter.input_last_prices = Vec::new();
 
     emit!(CreateMinterEvent {
       is_active: minter.is_active,
@@ -74,7 +127,7 @@ pub mod coin98_dollar_mint_burn {
     Ok(())
   }
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::ConfigManager, *ctx.accounts.root.key))]
   pub fn set_minter(
     ctx: Context<SetMinterContext>,
     is_active: bool,
@@ -82,56 +135,52 @@ pub mod coin98_dollar_mint_burn {
     input_decimals: Vec<u16>,
     input_percentages: Vec<u16>,
     input_price_feeds: Vec<Pubkey>,
+    input_price_configs: Vec<PriceConfig>,
     fee_percent: u16,
     total_minted_limit: u64,
     per_period_minted_limit: u64,
     min_amount: u64,
   ) -> Result<()> {
 
-    if input_tokens.len() != input_decimals.len() {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-    if input_tokens.len() != input_percentages.len() {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-    if input_tokens.len() != input_price_feeds.len() {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-    let percentage: u16 = input_percentages.iter().sum();
-    if percentage != 10000 {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-    if fee_percent > SYSTEM_FEE_CAP {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-
-    let minter = &mut ctx.accounts.minter;
-    minter.is_active = is_active;
-    minter.input_tokens = input_tokens.clone();
-    minter.input_decimals = input_decimals.clone();
-    minter.input_percentages = input_percentages.clone();
-    minter.input_price_feeds = input_price_feeds.clone();
-    minter.fee_percent = fee_percent;
-    minter.total_minted_limit = total_minted_limit;
-    minter.per_period_minted_limit = per_period_minted_limit;
-    minter.min_amount = min_amount;
-
-    emit!(SetMinterEvent {
+    let config = MinterConfig {
       is_active,
       input_tokens,
       input_decimals,
       input_percentages,
       input_price_feeds,
+      input_price_configs,
       fee_percent,
       total_minted_limit,
       per_period_minted_limit,
       min_amount,
+    };
+    validate_minter_config(&config)?;
+
+    let minter = &mut ctx.accounts.minter;
+    if is_minter_loosened(minter, &config) {
+      return Err(ErrorCode::TimelockRequired.into());
+    }
+    apply_minter_config(minter, config);
+
+    Ok(())
+  }
+
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Pauser, *ctx.accounts.root.key))]
+  pub fn deactivate_minter(
+    ctx: Context<DeactivateMinterContext>,
+  ) -> Result<()> {
+
+    let minter = &mut ctx.accounts.minter;
+    minter.is_active = false;
+
+    emit!(DeactivateMinterEvent {
+      minter: minter.key(),
     });
 
     Ok(())
   }
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::ConfigManager, *ctx.accounts.root.key))]
   pub fn create_burner(
     ctx: Context<CreateBurnerContext>,
     _derivation_path: Vec<u8>,
@@ -148,79 +197,152 @@ pub mod coin98_dollar_mint_burn {
     Ok(())
   }
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::ConfigManager, *ctx.accounts.root.key))]
   pub fn set_burner(
     ctx: Context<SetBurnerContext>,
     is_active: bool,
     output_token: Pubkey,
     output_decimals: u16,
     output_price_feed: Pubkey,
+    output_price_config: PriceConfig,
     fee_percent: u16,
     total_burned_limit: u64,
     per_period_burned_limit: u64,
     min_amount: u64,
   ) -> Result<()> {
 
-    if fee_percent > SYSTEM_FEE_CAP {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-
-    let burner = &mut ctx.accounts.burner;
-    burner.is_active = is_active;
-    burner.output_token = output_token;
-    burner.output_decimals = output_decimals;
-    burner.output_price_feed = output_price_feed;
-    burner.fee_percent = fee_percent;
-    burner.total_burned_limit = total_burned_limit;
-    burner.per_period_burned_limit = per_period_burned_limit;
-    burner.min_amount = min_amount;
-
-    emit!(SetBurnerEvent {
+    let config = BurnerConfig {
       is_active,
       output_token,
       output_decimals,
       output_price_feed,
+      output_price_config,
       fee_percent,
       total_burned_limit,
       per_period_burned_limit,
       min_amount,
+    };
+    validate_burner_config(&config)?;
+
+    let burner = &mut ctx.accounts.burner;
+    if is_burner_loosened(burner, &config) {
+      return Err(ErrorCode::TimelockRequired.into());
+    }
+    apply_burner_config(burner, config);
+
+    Ok(())
+  }
+
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Pauser, *ctx.accounts.root.key))]
+  pub fn deactivate_burner(
+    ctx: Context<DeactivateBurnerContext>,
+  ) -> Result<()> {
+
+    let burner = &mut ctx.accounts.burner;
+    burner.is_active = false;
+
+    emit!(DeactivateBurnerEvent {
+      burner: burner.key(),
     });
 
     Ok(())
   }
 
-pub fn mint<'a>(
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::ConfigManager, *ctx.accounts.root.key))]
+  pub fn create_twap(
+    ctx: Context<CreateTwapContext>,
+    window: u32,
+    price_config: PriceConfig, // validation of the prices recorded by poke, priced at spot
+  ) -> Result<()> {
+
+    let price_feed = &ctx.accounts.price_feed;
+    if price_config.oracle_type == OracleType::Fixed || price_config.price_mode != PriceMode::Spot || window == 0 {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    validate_price_config(&price_config, &price_feed.key())?;
+
+    let twap = &mut ctx.accounts.twap;
+    twap.nonce = *ctx.bumps.get("twap").unwrap();
+    twap.price_feed = price_feed.key();
+    twap.window = window;
+    twap.price_config = price_config.clone();
+
+    emit!(CreateTwapEvent {
+      twap: twap.key(),
+      price_feed: price_feed.key(),
+      window,
+      price_config,
+    });
+
+    Ok(())
+  }
+
+  // Records the validated price of the feed in the TWAP, mint and burn record it as well,
+  // remaining accounts are the extra price feeds of the TWAP price config, in order
+  pub fn poke<'a>(
+    ctx: Context<'_, '_, '_, 'a, PokeContext<'a>>,
+  ) -> Result<()> {
+
+    let app_data = &ctx.accounts.app_data;
+    let twap = &mut ctx.accounts.twap;
+    record_twap_price(app_data, &ctx.accounts.chainlink_program, &ctx.accounts.price_feed, ctx.remaining_accounts, twap)?;
+
+    Ok(())
+  }
+
+  pub fn mint<'a>(
     ctx: Context<'_, '_, '_, 'a, MintContext<'a>>,
     amount: u64, // amount of CUSD user want to mint
     extra_instructions: Vec<u8>,
+    max_input_amounts: Vec<u64>, // maximum amount of each input token user accept to pay
   ) -> Result<()> {
 
-    let user = &ctx.accounts.user;
-    let app_data = &ctx.accounts.app_data;
-    let root_signer = &ctx.accounts.root_signer;
     let minter = &ctx.accounts.minter;
-
-    if amount < minter.min_amount {
+    if max_input_amounts.len() != minter.input_tokens.len() {
       return Err(ErrorCode::InvalidInput.into());
     }
-    if !minter.is_active {
-      return Err(ErrorCode::Unavailable.into());
-    }
 
-    let current_timestamp = Clock::get().unwrap().unix_timestamp;
-    let timestamp_per_period = i64::from(app_data.limit) * 3600;
-    let is_in_period = minter.last_period_timestamp + timestamp_per_period > current_timestamp;
-    let current_period_minted_amount = if is_in_period { minter.per_period_minted_amount } else { 0u64 };
+    let account_indices: Vec<usize> = extra_instructions.iter()
+      .map(|extra| {
+        usize::from(*extra)
+      })
+      .collect();
+
+    let current_timestamp = get_current_timestamp()?;
+    let period = check_mint_limits(&ctx.accounts.app_data, minter, amount, current_timestamp)?;
 
-    if current_period_minted_amount + amount > minter.per_period_minted_limit {
-      return Err(ErrorCode::LimitReached.into());
+    let mut prices: Vec<OraclePrice> = Vec::new();
+    for i in 0..minter.input_tokens.len() {
+      prices.push(get_input_price(&ctx.accounts.app_data, minter, &ctx.accounts.chainlink_program, ctx.remaining_accounts, i, true)?);
     }
-    if minter.total_minted_amount + amount > minter.total_minted_limit {
-      return Err(ErrorCode::LimitReached.into());
+
+    let input_amounts = compute_input_amounts(minter, amount, &prices)?;
+    for i in 0..input_amounts.len() {
+      if input_amounts[i] > max_input_amounts[i] {
+        return Err(ErrorCode::MaxInputAmountExceeded.into());
+      }
     }
+    check_minter_circuit_breaker(minter, &prices, current_timestamp)?;
+    update_minter_reference_prices(&mut ctx.accounts.minter, &prices, current_timestamp)?;
 
-    let chainlink_program = &ctx.accounts.chainlink_program;
-    let accounts = &ctx.remaining_accounts;
+    for i in 0..input_amounts.len() {
+      transfer_input_token(&ctx, &account_indices, i, input_amounts[i])?;
+    }
+
+    process_mint(ctx, amount, input_amounts, &prices, period, current_timestamp)
+  }
+
+  pub fn mint_exact_input<'a>(
+    ctx: Context<'_, '_, '_, 'a, MintContext<'a>>,
+    input_amount: u64, // amount of input token user want to pay
+    extra_instructions: Vec<u8>,
+    min_amount_out: u64, // minimum amount of CUSD user accept to receive
+  ) -> Result<()> {
+
+    let minter = &ctx.accounts.minter;
+    if minter.input_tokens.len() != 1 {
+      return Err(ErrorCode::InvalidInput.into());
+    }
 
     let account_indices: Vec<usize> = extra_instructions.iter()
       .map(|extra| {
@@ -228,83 +350,54 @@ pub fn mint<'a>(
       })
       .collect();
 
-    for (i, input_token) in minter.input_tokens.iter().enumerate() {
-      let input_price_feed = &minter.input_price_feeds[i];
-      let (mut price, mut precision) = (1u64, 1u64);
-      let use_price_feed = *input_price_feed != system_program::ID;
-      if use_price_feed {
-        let price_feed = &accounts[3*i];
-        if price_feed.key() != *input_price_feed {
-          return Err(ErrorCode::InvalidAccount.into());
-        }
-        (price, precision) = get_price_feed(
-            &*chainlink_program,
-            &*price_feed,
-          );
-      }
-      else {
-        let clock = Clock::get().unwrap();
-        msg!("Price fetched: {}/{} at {} in block {}", price, precision, clock.unix_timestamp, clock.slot);
-      }
-
-      let value_contrib = minter.input_percentages[i];
+    let price = get_input_price(&ctx.accounts.app_data, minter, &ctx.accounts.chainlink_program, ctx.remaining_accounts, 0, true)?;
 
-      let input_value = amount.checked_mul(u64::from(value_contrib)).unwrap().checked_div(10000).unwrap();
-      let input_amount = multiply_fraction(input_value, precision, price);
-      let input_precision = u64::pow(10, u32::from(minter.input_decimals[i]));
-      let input_amount = multiply_fraction(input_amount, input_precision, CUSD_PRECISION);
+    let input_precision = checked_pow10(u32::from(minter.input_decimals[0]))?;
+    let amount = checked_mul_div(input_amount, CUSD_PRECISION, input_precision, Rounding::Floor)?;
+    let amount = checked_mul_div(amount, price.min_value(), price.precision(), Rounding::Floor)?;
+    let protocol_fee = checked_mul_div(amount, u64::from(minter.fee_percent), 10000, Rounding::Ceil)?;
+    if checked_sub(amount, protocol_fee)? < min_amount_out {
+      return Err(ErrorCode::MinAmountOutNotMet.into());
+    }
 
-      let from_account_index = account_indices[3*i + 1];
-      let to_account_index = account_indices[3*i + 2];
-      let from_account = &accounts[from_account_index];
-      let from_account = TokenAccount::unpack_from_slice(&from_account.try_borrow_data().unwrap()).unwrap();
-      let to_account = &accounts[to_account_index];
-      let to_account = TokenAccount::unpack_from_slice(&to_account.try_borrow_data().unwrap()).unwrap();
-      if from_account.mint != *input_token {
-        return Err(ErrorCode::InvalidAccount.into());
-      }
-      if to_account.mint != *input_token || to_account.owner != root_signer.key() {
-        return Err(ErrorCode::InvalidAccount.into());
-      }
+    let current_timestamp = get_current_timestamp()?;
+    let period = check_mint_limits(&ctx.accounts.app_data, minter, amount, current_timestamp)?;
+    check_minter_circuit_breaker(minter, &[price], current_timestamp)?;
+    update_minter_reference_prices(&mut ctx.accounts.minter, &[price], current_timestamp)?;
 
-      transfer_token(
-          &*user,
-          &accounts[from_account_index],
-          &accounts[to_account_index],
-          input_amount,
-          &[],
-        )
-        .expect("CUSD Factory: CPI failed.");
-    }
+    transfer_input_token(&ctx, &account_indices, 0, input_amount)?;
 
-    let minter = &mut ctx.accounts.minter;
-    minter.total_minted_amount = minter.total_minted_amount + amount;
-    minter.per_period_minted_amount = current_period_minted_amount + amount;
-    if !is_in_period {
-      minter.last_period_timestamp = current_timestamp;
-    }
+    process_mint(ctx, amount, Vec::from([input_amount]), &[price], period, current_timestamp)
+  }
 
-    let protocol_fee = multiply_fraction(amount, u64::from(minter.fee_percent), 10000);
-    let amount_to_transfer = amount.checked_sub(protocol_fee).unwrap();
-    minter.accumulated_fee = minter.accumulated_fee.checked_add(protocol_fee).unwrap();
+  // Runs the pricing and limit checks of mint without moving funds and sets a MintResult as return data,
+  // remaining accounts follow the layout of mint, TWAP accounts are read but not updated
+  pub fn quote_mint<'a>(
+    ctx: Context<'_, '_, '_, 'a, QuoteMintContext<'a>>,
+    amount: u64, // amount of CUSD user want to mint
+  ) -> Result<()> {
 
-    let cusd_mint = &ctx.accounts.cusd_mint;
-    let recipient = &ctx.accounts.recipient;
+    let app_data = &ctx.accounts.app_data;
+    let minter = &ctx.accounts.minter;
 
-    let seeds: &[&[u8]] = &[
-      ROOT_SIGNER_SEED_1,
-      ROOT_SIGNER_SEED_2,
-      &[app_data.signer_nonce],
-    ];
+    let current_timestamp = get_current_timestamp()?;
+    check_mint_limits(app_data, minter, amount, current_timestamp)?;
 
-    mint_token(
-        &*root_signer,
-        &*cusd_mint,
-        &*recipient,
-        amount_to_transfer,
-        &[&seeds],
-      )
-      .expect("CUSD Factory: CPI failed.");
+    let mut prices: Vec<OraclePrice> = Vec::new();
+    for i in 0..minter.input_tokens.len() {
+      prices.push(get_input_price(app_data, minter, &ctx.accounts.chainlink_program, ctx.remaining_accounts, i, false)?);
+    }
+    check_minter_circuit_breaker(minter, &prices, current_timestamp)?;
+
+    let protocol_fee = checked_mul_div(amount, u64::from(minter.fee_percent), 10000, Rounding::Ceil)?;
+    let result = MintResult {
+      input_amounts: compute_input_amounts(minter, amount, &prices)?,
+      input_prices: prices.iter().map(|price| price.min_value()).collect(),
+      input_precisions: prices.iter().map(|price| price.precision()).collect(),
+      amount_out: checked_sub(amount, protocol_fee)?,
+      fee: protocol_fee,
+    };
+    set_result(&result)?;
 
     Ok(())
   }
@@ -312,178 +405,175 @@ pub fn mint<'a>(
   pub fn burn<'a>(
     ctx: Context<'_, '_, '_, 'a, BurnContext<'a>>,
     amount: u64, // amount of output_token user want to burn
+    max_cusd_in: u64, // maximum amount of CUSD user accept to burn
+  ) -> Result<()> {
+
+    let price = get_output_price(&ctx.accounts.app_data, &ctx.accounts.burner, &ctx.accounts.chainlink_program, ctx.remaining_accounts, true)?;
+
+    let cusd_amount = compute_burn_cusd_amount(&ctx.accounts.burner, amount, &price)?;
+    if cusd_amount > max_cusd_in {
+      return Err(ErrorCode::MaxCusdInExceeded.into());
+    }
+
+    let current_timestamp = get_current_timestamp()?;
+    let period = check_burn_limits(&ctx.accounts.app_data, &ctx.accounts.burner, amount, cusd_amount, current_timestamp)?;
+    check_burner_circuit_breaker(&ctx.accounts.burner, &price, current_timestamp)?;
+    update_burner_reference_price(&mut ctx.accounts.burner, &price, current_timestamp)?;
+
+    process_burn(ctx, amount, cusd_amount, &price, period, current_timestamp)
+  }
+
+  // Runs the pricing and limit checks of burn without moving funds and sets a BurnResult as return data,
+  // remaining accounts follow the layout of burn, TWAP account is read but not updated
+  pub fn quote_burn<'a>(
+    ctx: Context<'_, '_, '_, 'a, QuoteBurnContext<'a>>,
+    amount: u64, // amount of output_token user want to burn
   ) -> Result<()> {
 
-    let user = &ctx.accounts.user;
     let app_data = &ctx.accounts.app_data;
     let burner = &ctx.accounts.burner;
-    let chainlink_program = &ctx.accounts.chainlink_program;
-    let accounts = &ctx.remaining_accounts;
-    let price_feed = &accounts[0];
 
-    if amount < burner.min_amount {
-      return Err(ErrorCode::InvalidInput.into());
-    }
-    if !burner.is_active {
-      return Err(ErrorCode::Unavailable.into());
-    }
+    let price = get_output_price(app_data, burner, &ctx.accounts.chainlink_program, ctx.remaining_accounts, false)?;
 
-    let current_timestamp = Clock::get().unwrap().unix_timestamp;
-    let timestamp_per_period = i64::from(app_data.limit) * 3600;
-    let is_in_period = burner.last_period_timestamp + timestamp_per_period > current_timestamp;
-    let current_period_burned_amount = if is_in_period { burner.per_period_burned_amount } else { 0u64 };
+    let cusd_amount = compute_burn_cusd_amount(burner, amount, &price)?;
+    let current_timestamp = get_current_timestamp()?;
+    check_burn_limits(app_data, burner, amount, cusd_amount, current_timestamp)?;
+    check_burner_circuit_breaker(burner, &price, current_timestamp)?;
 
-    let (mut price, mut precision) = (1u64, 1u64);
-    let use_price_feed = burner.output_price_feed != system_program::ID;
-    if use_price_feed {
-      if price_feed.key() != burner.output_price_feed {
-        return Err(ErrorCode::InvalidAccount.into());
-      }
-      (price, precision) = get_price_feed(
-        &*chainlink_program,
-        &*price_feed,
-      );
-    }
-    else {
-      let clock = Clock::get().unwrap();
-      msg!("Price fetched: {}/{} at {} in block {}", price, precision, clock.unix_timestamp, clock.slot);
-    }
+    let protocol_fee = checked_mul_div(amount, u64::from(burner.fee_percent), 10000, Rounding::Ceil)?;
+    let result = BurnResult {
+      cusd_amount_in: cusd_amount,
+      amount_out: checked_sub(amount, protocol_fee)?,
+      output_price: price.max_value()?,
+      output_precision: price.precision(),
+      fee: protocol_fee,
+    };
+    set_result(&result)?;
 
-    let cusd_amount = multiply_fraction(amount, price, precision);
-    let output_precision = u64::pow(10, u32::from(burner.output_decimals));
-    let cusd_amount = multiply_fraction(cusd_amount, CUSD_PRECISION, output_precision);
+    Ok(())
+  }
 
-    if current_period_burned_amount + cusd_amount > burner.per_period_burned_limit {
-      return Err(ErrorCode::LimitReached.into());
-    }
-    if burner.total_burned_amount + cusd_amount > burner.total_burned_limit {
-      return Err(ErrorCode::LimitReached.into());
-    }
+  pub fn burn_cusd<'a>(
+    ctx: Context<'_, '_, '_, 'a, BurnContext<'a>>,
+    cusd_amount: u64, // amount of CUSD user want to burn
+    min_amount_out: u64, // minimum amount of output_token user accept to receive
+  ) -> Result<()> {
 
-    let pool_cusd = &ctx.accounts.pool_cusd;
-    let user_cusd = &ctx.accounts.user_cusd;
-    transfer_token(
-        &*user,
-        &user_cusd.to_account_info(),
-        &pool_cusd.to_account_info(),
-        cusd_amount,
-        &[],
-      )
-      .expect("CUSD Factory: CPI failed.");
+    let price = get_output_price(&ctx.accounts.app_data, &ctx.accounts.burner, &ctx.accounts.chainlink_program, ctx.remaining_accounts, true)?;
 
-    let root_signer = &ctx.accounts.root_signer;
-    let cusd_mint = &ctx.accounts.cusd_mint;
-    let seeds: &[&[u8]] = &[
-      ROOT_SIGNER_SEED_1,
-      ROOT_SIGNER_SEED_2,
-      &[app_data.signer_nonce],
-    ];
-    burn_token(
-        &*root_signer,
-        &*cusd_mint,
-        &pool_cusd.to_account_info(),
-        cusd_amount,
-        &[&seeds],
-      )
-      .expect("CUSD Factory: CPI failed.");
+    let burner = &ctx.accounts.burner;
 
-    let burner = &mut ctx.accounts.burner;
-    burner.total_burned_amount = burner.total_burned_amount + cusd_amount;
-    burner.per_period_burned_amount = current_period_burned_amount + cusd_amount;
-    if !is_in_period {
-      burner.last_period_timestamp = current_timestamp;
-    }
-    let protocol_fee = multiply_fraction(amount, u64::from(burner.fee_percent), 10000);
-    let amount_to_transfer = amount.checked_sub(protocol_fee).unwrap();
-    burner.accumulated_fee = burner.accumulated_fee.checked_add(protocol_fee).unwrap();
-
-    let pool_token = &accounts[1];
-    let pool_token = TokenAccount::unpack_from_slice(&pool_token.try_borrow_data().unwrap()).unwrap();
-    if pool_token.owner != root_signer.key() || pool_token.mint != burner.output_token {
-      return Err(ErrorCode::InvalidAccount.into());
+    let output_precision = checked_pow10(u32::from(burner.output_decimals))?;
+    let amount = checked_mul_div(cusd_amount, output_precision, CUSD_PRECISION, Rounding::Floor)?;
+    let amount = checked_mul_div(amount, price.precision(), price.max_value()?, Rounding::Floor)?;
+    let protocol_fee = checked_mul_div(amount, u64::from(burner.fee_percent), 10000, Rounding::Ceil)?;
+    if checked_sub(amount, protocol_fee)? < min_amount_out {
+      return Err(ErrorCode::MinAmountOutNotMet.into());
     }
-    let user_token = &accounts[2];
-    let user_token = TokenAccount::unpack_from_slice(&user_token.try_borrow_data().unwrap()).unwrap();
-    if user_token.mint != burner.output_token {
-      return Err(ErrorCode::InvalidAccount.into());
+
+    let current_timestamp = get_current_timestamp()?;
+    let period = check_burn_limits(&ctx.accounts.app_data, burner, amount, cusd_amount, current_timestamp)?;
+    check_burner_circuit_breaker(burner, &price, current_timestamp)?;
+    update_burner_reference_price(&mut ctx.accounts.burner, &price, current_timestamp)?;
+
+    process_burn(ctx, amount, cusd_amount, &price, period, current_timestamp)
+  }
+
+  // Permissionless, pauses a minter configured with pause_on_deviation once one of its prices deviates,
+  // mint fails with CircuitBreakerTripped until then, remaining accounts follow the layout of mint
+  pub fn trip_minter_circuit_breaker<'a>(
+    ctx: Context<'_, '_, '_, 'a, TripMinterCircuitBreakerContext<'a>>,
+  ) -> Result<()> {
+
+    let app_data = &ctx.accounts.app_data;
+    let minter = &ctx.accounts.minter;
+    let current_timestamp = get_current_timestamp()?;
+    for i in 0..minter.input_tokens.len() {
+      let price_config = &minter.input_price_configs[i];
+      if !price_config.pause_on_deviation {
+        continue;
+      }
+      let price = get_input_price(app_data, minter, &ctx.accounts.chainlink_program, ctx.remaining_accounts, i, false)?;
+      let cusd_price = checked_mul_div(price.value, CUSD_PRECISION, price.precision(), Rounding::Floor)?;
+      let last_price = minter.input_last_prices[i];
+      if let Some(deviation) = price_deviation(price_config, &last_price, cusd_price, current_timestamp) {
+        let token = minter.input_tokens[i];
+        let minter = &mut ctx.accounts.minter;
+        minter.is_active = false;
+
+        emit!(CircuitBreakerTrippedEvent {
+          authority: minter.key(),
+          token,
+          last_price: last_price.price,
+          price: cusd_price,
+          deviation,
+          is_paused: true,
+        });
+
+        return Ok(());
+      }
     }
-    transfer_token(
-        &*root_signer,
-        &accounts[1],
-        &accounts[2],
-        amount_to_transfer,
-        &[&seeds],
-      )
-      .expect("CUSD Factory: CPI failed.");
 
-    Ok(())
+    Err(ErrorCode::InvalidInput.into())
   }
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
-  pub fn withdraw_token(
-    ctx: Context<WithdrawTokenContext>,
-    amount: u64,
+  // Same as trip_minter_circuit_breaker for the output token of a burner, remaining accounts follow the layout of burn
+  pub fn trip_burner_circuit_breaker<'a>(
+    ctx: Context<'_, '_, '_, 'a, TripBurnerCircuitBreakerContext<'a>>,
   ) -> Result<()> {
 
     let app_data = &ctx.accounts.app_data;
-    let root_signer = &ctx.accounts.root_signer;
-    let pool_token = &ctx.accounts.pool_token;
-    let recipient_token = &ctx.accounts.recipient_token;
-    let seeds: &[&[u8]] = &[
-      ROOT_SIGNER_SEED_1,
-      ROOT_SIGNER_SEED_2,
-      &[app_data.signer_nonce],
-    ];
+    let burner = &ctx.accounts.burner;
+    if !burner.output_price_config.pause_on_deviation {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    let price = get_output_price(app_data, burner, &ctx.accounts.chainlink_program, ctx.remaining_accounts, false)?;
 
-    transfer_token(
-        &*root_signer,
-        &pool_token.to_account_info(),
-        &recipient_token.to_account_info(),
-        amount,
-        &[&seeds],
-      )
-      .expect("CUSD Factory: CPI failed.");
+    let current_timestamp = get_current_timestamp()?;
+    let cusd_price = checked_mul_div(price.value, CUSD_PRECISION, price.precision(), Rounding::Floor)?;
+    let last_price = burner.output_last_price;
+    let deviation = price_deviation(&burner.output_price_config, &last_price, cusd_price, current_timestamp)
+      .ok_or(ErrorCode::InvalidInput)?;
 
-    emit!(WithdrawTokenEvent {
-      recipient_token_account: *ctx.accounts.recipient_token.to_account_info().key,
-      amount,
+    let burner = &mut ctx.accounts.burner;
+    burner.is_active = false;
+
+    emit!(CircuitBreakerTrippedEvent {
+      authority: burner.key(),
+      token: burner.output_token,
+      last_price: last_price.price,
+      price: cusd_price,
+      deviation,
+      is_paused: true,
     });
 
     Ok(())
   }
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
+  // Deprecated, withdrawals go through the timelock as Operation::WithdrawToken
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Treasurer, *ctx.accounts.root.key))]
+  pub fn withdraw_token(
+    ctx: Context<WithdrawTokenContext>,
+    _amount: u64,
+  ) -> Result<()> {
+
+    msg!("CUSD Factory: withdraw_token is deprecated, queue Operation::WithdrawToken instead");
+
+    Err(ErrorCode::TimelockRequired.into())
+  }
+
+  // Deprecated, mint authority transfers go through the timelock as Operation::UnlockTokenMint
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::MintAuthorityCustodian, *ctx.accounts.root.key))]
   pub fn unlock_token_mint(
     ctx: Context<UnlockTokenMintContext>,
   ) -> Result<()> {
 
-    let root = &ctx.accounts.root;
-    let app_data = &ctx.accounts.app_data;
-    let root_signer = &ctx.accounts.root_signer;
-    let token_mint = &ctx.accounts.token_mint;
-    let seeds: &[&[u8]] = &[
-      ROOT_SIGNER_SEED_1,
-      ROOT_SIGNER_SEED_2,
-      &[app_data.signer_nonce],
-    ];
-    transfer_authority(
-        &*root_signer,
-        &token_mint.to_account_info(),
-        0,
-        &*root,
-        &[&seeds],
-      )
-      .expect("CUSD Factory: CPI failed.");
-
-    emit!(UnlockTokenMintEvent {
-      token_mint: *token_mint.to_account_info().key,
-      new_authority: *root_signer.key,
-    });
+    msg!("CUSD Factory: unlock_token_mint is deprecated, queue Operation::UnlockTokenMint instead");
 
-    Ok(())
+    Err(ErrorCode::TimelockRequired.into())
   }
 
-  #[access_control(is_root(*ctx.accounts.root.key))]
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
   pub fn create_app_data(
     ctx: Context<CreateAppDataContext>,
   ) -> Result<()> {
@@ -499,40 +589,1770 @@ pub fn mint<'a>(
     );
     app_data.signer_nonce = signer_nonce;
     app_data.limit = 24;
+    app_data.timelock_delay = DEFAULT_TIMELOCK_DELAY;
+    app_data.is_paused = false;
+    app_data.is_mint_paused = false;
+    app_data.is_burn_paused = false;
+    app_data.max_price_age = DEFAULT_MAX_PRICE_AGE;
+    app_data.treasury = ctx.accounts.root.key();
+    let (_, fee_signer_nonce) = Pubkey::find_program_address(
+      &[
+        FEE_SIGNER_SEED_1,
+        FEE_SIGNER_SEED_2,
+      ],
+      ctx.program_id,
+    );
+    app_data.fee_signer_nonce = fee_signer_nonce;
 
     Ok(())
   }
 
+  // Only instruction gated by ROOT_KEYS, runs once to seat the first admin before create_app_data,
+  // deployments that already have an AppData bootstrap the registry the same way
   #[access_control(is_root(*ctx.accounts.root.key))]
-  pub fn set_app_data(
-    ctx: Context<SetAppDataContext>,
-    limit: u32,
+  pub fn create_admin_registry(
+    ctx: Context<CreateAdminRegistryContext>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
   ) -> Result<()> {
 
-    let app_data = &mut ctx.accounts.app_data;
-    app_data.limit = limit;
+    if signers.len() > MAX_ROLE_MEMBER_COUNT {
+      return Err(ErrorCode::LimitReached.into());
+    }
+    for (i, signer) in signers.iter().enumerate() {
+      if *signer == Pubkey::default() || signers[..i].contains(signer) {
+        return Err(ErrorCode::InvalidInput.into());
+      }
+    }
+    if threshold == 0 || usize::from(threshold) > signers.len() {
+      return Err(ErrorCode::InvalidInput.into());
+    }
 
-    emit!(SetAppDataEvent {
-      limit,
+    let root = &ctx.accounts.root;
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    admin_registry.nonce = *ctx.bumps.get("admin_registry").unwrap();
+    admin_registry.admins = Vec::from([root.key()]);
+    admin_registry.signers = signers.clone();
+    admin_registry.threshold = threshold;
+
+    emit!(AddAdminEvent {
+      admin: root.key(),
     });
+    for member in signers {
+      emit!(GrantRoleEvent {
+        role: Role::Signer,
+        member,
+      });
+    }
 
     Ok(())
   }
-}
 
-pub fn is_root(user: Pubkey) -> Result<()> {
-  let user_key = user.to_string();
-  let result = ROOT_KEYS.iter().position(|&key| key == &user_key[..]);
-  if result == None {
-    return Err(ErrorCode::Unauthorized.into());
-  }
+  // Grows an AppData created before the timelock to the current layout, the new fields start with their defaults
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn migrate_app_data(
+    ctx: Context<MigrateAppDataContext>,
+  ) -> Result<()> {
 
-  Ok(())
-}
+    let root = &ctx.accounts.root;
+    let app_data_info = &ctx.accounts.app_data;
+    if *app_data_info.owner != *ctx.program_id {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+    let new_len = 16 + AppData::LEN;
+    if app_data_info.data_len() >= new_len {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    let legacy = {
+      let data = app_data_info.try_borrow_data()?;
+      if data.len() < 8 + LegacyAppData::LEN || data[..8] != AppData::discriminator() {
+        return Err(ErrorCode::InvalidAccount.into());
+      }
+      LegacyAppData::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::InvalidAccount)?
+    };
+
+    realloc_account(root, app_data_info, &ctx.accounts.system_program.to_account_info(), new_len)?;
 
-fn multiply_fraction(number: u64, numerator: u64, denominator: u64) -> u64 {
-  let number_128 = u128::from(number)
-    .checked_mul(u128::from(numerator)).unwrap()
-    .checked_div(u128::from(denominator)).unwrap();
-  u64::try_from(number_128).unwrap()
+    let (_, fee_signer_nonce) = Pubkey::find_program_address(
+      &[
+        FEE_SIGNER_SEED_1,
+        FEE_SIGNER_SEED_2,
+      ],
+      ctx.program_id,
+    );
+    let app_data = AppData {
+      nonce: legacy.nonce,
+      signer_nonce: legacy.signer_nonce,
+      limit: legacy.limit,
+      timelock_delay: DEFAULT_TIMELOCK_DELAY,
+      is_paused: false,
+      is_mint_paused: false,
+      is_burn_paused: false,
+      max_price_age: DEFAULT_MAX_PRICE_AGE,
+      treasury: root.key(),
+      fee_signer_nonce,
+    };
+    let mut data = app_data_info.try_borrow_mut_data()?;
+    app_data.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+  }
+
+  // Grows a Minter created before price configs to the current layout, its feeds keep the pricing they had
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn migrate_minter(
+    ctx: Context<MigrateMinterContext>,
+  ) -> Result<()> {
+
+    let root = &ctx.accounts.root;
+    let minter_info = &ctx.accounts.minter;
+    if *minter_info.owner != *ctx.program_id {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+    let new_len = 16 + Minter::size(MAX_INPUT_TOKEN_COUNT as u8);
+    if minter_info.data_len() >= new_len {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    let legacy = {
+      let data = minter_info.try_borrow_data()?;
+      if data.len() < 8 || data[..8] != Minter::discriminator() {
+        return Err(ErrorCode::InvalidAccount.into());
+      }
+      LegacyMinter::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::InvalidAccount)?
+    };
+
+    realloc_account(root, minter_info, &ctx.accounts.system_program.to_account_info(), new_len)?;
+
+    let token_count = legacy.input_tokens.len();
+    let minter = Minter {
+      nonce: legacy.nonce,
+      is_active: legacy.is_active,
+      input_tokens: legacy.input_tokens,
+      input_decimals: legacy.input_decimals,
+      input_percentages: legacy.input_percentages,
+      input_price_configs: legacy.input_price_feeds.iter().map(legacy_price_config).collect(),
+      input_price_feeds: legacy.input_price_feeds,
+      input_last_prices: vec![AcceptedPrice::default(); token_count],
+      fee_percent: legacy.fee_percent,
+      accumulated_fee: legacy.accumulated_fee,
+      total_minted_amount: legacy.total_minted_amount,
+      total_minted_limit: legacy.total_minted_limit,
+      per_period_minted_amount: legacy.per_period_minted_amount,
+      per_period_minted_limit: legacy.per_period_minted_limit,
+      last_period_timestamp: legacy.last_period_timestamp,
+      min_amount: legacy.min_amount,
+    };
+    let mut data = minter_info.try_borrow_mut_data()?;
+    minter.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+  }
+
+  // Grows a Burner created before price configs to the current layout, its feed keeps the pricing it had
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn migrate_burner(
+    ctx: Context<MigrateBurnerContext>,
+  ) -> Result<()> {
+
+    let root = &ctx.accounts.root;
+    let burner_info = &ctx.accounts.burner;
+    if *burner_info.owner != *ctx.program_id {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+    let new_len = 16 + Burner::LEN;
+    if burner_info.data_len() >= new_len {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    let legacy = {
+      let data = burner_info.try_borrow_data()?;
+      if data.len() < 8 || data[..8] != Burner::discriminator() {
+        return Err(ErrorCode::InvalidAccount.into());
+      }
+      LegacyBurner::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::InvalidAccount)?
+    };
+
+    realloc_account(root, burner_info, &ctx.accounts.system_program.to_account_info(), new_len)?;
+
+    let burner = Burner {
+      nonce: legacy.nonce,
+      is_active: legacy.is_active,
+      output_token: legacy.output_token,
+      output_decimals: legacy.output_decimals,
+      output_price_feed: legacy.output_price_feed,
+      output_price_config: legacy_price_config(&legacy.output_price_feed),
+      output_last_price: AcceptedPrice::default(),
+      fee_percent: legacy.fee_percent,
+      // fees of earlier burns were never moved out of the pool and stay part of the reserve
+      accumulated_fee: 0,
+      total_burned_amount: legacy.total_burned_amount,
+      total_burned_limit: legacy.total_burned_limit,
+      per_period_burned_amount: legacy.per_period_burned_amount,
+      per_period_burned_limit: legacy.per_period_burned_limit,
+      last_period_timestamp: legacy.last_period_timestamp,
+      min_amount: legacy.min_amount,
+    };
+    let mut data = burner_info.try_borrow_mut_data()?;
+    burner.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+  }
+
+  // Only tightening takes effect right away, a shorter period, shorter delay or longer price age must be queued
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::ConfigManager, *ctx.accounts.root.key))]
+  pub fn set_app_data(
+    ctx: Context<SetAppDataContext>,
+    limit: u32,
+    timelock_delay: u32,
+    max_price_age: u32,
+  ) -> Result<()> {
+
+    let app_data = &mut ctx.accounts.app_data;
+    if limit < app_data.limit || timelock_delay < app_data.timelock_delay || max_price_age > app_data.max_price_age {
+      return Err(ErrorCode::TimelockRequired.into());
+    }
+    app_data.limit = limit;
+    app_data.timelock_delay = timelock_delay;
+    app_data.max_price_age = max_price_age;
+
+    emit!(SetAppDataEvent {
+      limit,
+      timelock_delay,
+      max_price_age,
+    });
+
+    Ok(())
+  }
+
+  // Guardians can only raise pause flags, lowering them is reserved for admins
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Pauser, *ctx.accounts.root.key))]
+  pub fn pause(
+    ctx: Context<PauseContext>,
+    is_paused: bool,
+    is_mint_paused: bool,
+    is_burn_paused: bool,
+  ) -> Result<()> {
+
+    let app_data = &mut ctx.accounts.app_data;
+    app_data.is_paused = app_data.is_paused || is_paused;
+    app_data.is_mint_paused = app_data.is_mint_paused || is_mint_paused;
+    app_data.is_burn_paused = app_data.is_burn_paused || is_burn_paused;
+
+    emit!(SetPauseEvent {
+      is_paused: app_data.is_paused,
+      is_mint_paused: app_data.is_mint_paused,
+      is_burn_paused: app_data.is_burn_paused,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn set_pause(
+    ctx: Context<SetPauseContext>,
+    is_paused: bool,
+    is_mint_paused: bool,
+    is_burn_paused: bool,
+  ) -> Result<()> {
+
+    let app_data = &mut ctx.accounts.app_data;
+    app_data.is_paused = is_paused;
+    app_data.is_mint_paused = is_mint_paused;
+    app_data.is_burn_paused = is_burn_paused;
+
+    emit!(SetPauseEvent {
+      is_paused,
+      is_mint_paused,
+      is_burn_paused,
+    });
+
+    Ok(())
+  }
+
+  // The new admin only takes a seat once it calls accept_admin, so the registry never holds a key nobody controls
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn add_admin(
+    ctx: Context<AddAdminContext>,
+    admin: Pubkey,
+  ) -> Result<()> {
+
+    let root = &ctx.accounts.root;
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    if admin == Pubkey::default() || admin_registry.is_admin(&admin) {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    // an in-flight proposal must be accepted or cancelled first
+    if admin_registry.pending_admin != Pubkey::default() {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    if admin_registry.admins.len() >= MAX_ADMIN_COUNT {
+      return Err(ErrorCode::LimitReached.into());
+    }
+    // no proposer seat to hand over, accept_admin adds a new one
+    admin_registry.pending_admin = admin;
+    admin_registry.pending_admin_proposer = Pubkey::default();
+
+    emit!(ProposeAdminEvent {
+      proposer: root.key(),
+      pending_admin: admin,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn remove_admin(
+    ctx: Context<RemoveAdminContext>,
+    admin: Pubkey,
+  ) -> Result<()> {
+
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    let index = admin_registry.admins.iter().position(|key| *key == admin);
+    if index == None {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    // Registry must never be left without an admin
+    if admin_registry.admins.len() == 1 {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    admin_registry.admins.remove(index.unwrap());
+
+    emit!(RemoveAdminEvent {
+      admin,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn propose_admin(
+    ctx: Context<ProposeAdminContext>,
+    pending_admin: Pubkey,
+  ) -> Result<()> {
+
+    let root = &ctx.accounts.root;
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    if pending_admin == Pubkey::default() || admin_registry.is_admin(&pending_admin) {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    if admin_registry.pending_admin != Pubkey::default() {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    admin_registry.pending_admin = pending_admin;
+    admin_registry.pending_admin_proposer = root.key();
+
+    emit!(ProposeAdminEvent {
+      proposer: root.key(),
+      pending_admin,
+    });
+
+    Ok(())
+  }
+
+  pub fn accept_admin(
+    ctx: Context<AcceptAdminContext>,
+  ) -> Result<()> {
+
+    let pending_admin = &ctx.accounts.pending_admin;
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    if admin_registry.pending_admin == Pubkey::default() {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    if admin_registry.pending_admin != pending_admin.key() {
+      return Err(ErrorCode::Unauthorized.into());
+    }
+
+    // The new admin takes over the proposer's seat, or a new seat when added with add_admin
+    let previous_admin = admin_registry.pending_admin_proposer;
+    let index = admin_registry.admins.iter().position(|key| *key == previous_admin);
+    match index {
+      Some(index) => {
+        admin_registry.admins[index] = pending_admin.key();
+      },
+      None => {
+        if admin_registry.admins.len() >= MAX_ADMIN_COUNT {
+          return Err(ErrorCode::LimitReached.into());
+        }
+        admin_registry.admins.push(pending_admin.key());
+      },
+    }
+    admin_registry.pending_admin = Pubkey::default();
+    admin_registry.pending_admin_proposer = Pubkey::default();
+
+    emit!(AcceptAdminEvent {
+      previous_admin,
+      admin: pending_admin.key(),
+    });
+
+    Ok(())
+  }
+
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn cancel_admin_transfer(
+    ctx: Context<CancelAdminTransferContext>,
+  ) -> Result<()> {
+
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    let pending_admin = admin_registry.pending_admin;
+    if pending_admin == Pubkey::default() {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    admin_registry.pending_admin = Pubkey::default();
+    admin_registry.pending_admin_proposer = Pubkey::default();
+
+    emit!(CancelAdminTransferEvent {
+      pending_admin,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn grant_role(
+    ctx: Context<GrantRoleContext>,
+    role: Role,
+    member: Pubkey,
+  ) -> Result<()> {
+
+    // Signers approve timelocked operations, adding one must itself be approved as Operation::GrantSigner
+    if role == Role::Signer {
+      return Err(ErrorCode::TimelockRequired.into());
+    }
+
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    if admin_registry.has_role(role, &member) {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    let members = admin_registry.members_mut(role);
+    if members.len() >= MAX_ROLE_MEMBER_COUNT {
+      return Err(ErrorCode::LimitReached.into());
+    }
+    members.push(member);
+
+    emit!(GrantRoleEvent {
+      role,
+      member,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(is_admin(&ctx.accounts.admin_registry, *ctx.accounts.root.key))]
+  pub fn revoke_role(
+    ctx: Context<RevokeRoleContext>,
+    role: Role,
+    member: Pubkey,
+  ) -> Result<()> {
+
+    let admin_registry = &mut ctx.accounts.admin_registry;
+    let threshold = usize::from(admin_registry.threshold);
+    let members = admin_registry.members_mut(role);
+    let index = members.iter().position(|key| *key == member);
+    if index == None {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    // Approval threshold must stay reachable
+    if role == Role::Signer && members.len() <= threshold {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    members.remove(index.unwrap());
+
+    emit!(RevokeRoleEvent {
+      role,
+      member,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(has_role(&ctx.accounts.admin_registry, operation.required_role(), *ctx.accounts.root.key))]
+  pub fn queue_operation(
+    ctx: Context<QueueOperationContext>,
+    _derivation_path: Vec<u8>,
+    operation: Operation,
+  ) -> Result<()> {
+
+    // arguments are checked again on execution, rejecting them now saves signers a wasted delay
+    match &operation {
+      Operation::SetMinter { config, .. } => validate_minter_config(config)?,
+      Operation::SetBurner { config, .. } => validate_burner_config(config)?,
+      Operation::SetThreshold { threshold } => validate_threshold(&ctx.accounts.admin_registry, *threshold)?,
+      _ => {},
+    }
+
+    let root = &ctx.accounts.root;
+    let app_data = &ctx.accounts.app_data;
+    let current_timestamp = get_current_timestamp()?;
+    let eta = current_timestamp + i64::from(app_data.timelock_delay);
+
+    let timelock_operation = &mut ctx.accounts.timelock_operation;
+    timelock_operation.nonce = *ctx.bumps.get("timelock_operation").unwrap();
+    timelock_operation.proposer = root.key();
+    timelock_operation.operation = operation.clone();
+    timelock_operation.eta = eta;
+    timelock_operation.approvals = Vec::new();
+    timelock_operation.is_executed = false;
+    timelock_operation.is_cancelled = false;
+
+    emit!(QueueOperationEvent {
+      timelock_operation: timelock_operation.key(),
+      proposer: root.key(),
+      operation,
+      eta,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Signer, *ctx.accounts.root.key))]
+  pub fn approve_operation(
+    ctx: Context<ApproveOperationContext>,
+  ) -> Result<()> {
+
+    let root = &ctx.accounts.root;
+    let admin_registry = &ctx.accounts.admin_registry;
+    let timelock_operation = &mut ctx.accounts.timelock_operation;
+    if timelock_operation.is_executed || timelock_operation.is_cancelled {
+      return Err(ErrorCode::OperationFinalized.into());
+    }
+    if timelock_operation.approvals.contains(&root.key()) {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+    if timelock_operation.approvals.len() >= MAX_ROLE_MEMBER_COUNT {
+      return Err(ErrorCode::LimitReached.into());
+    }
+    timelock_operation.approvals.push(root.key());
+    let approval_count = timelock_operation.approval_count(admin_registry);
+
+    emit!(ApproveOperationEvent {
+      timelock_operation: timelock_operation.key(),
+      approver: root.key(),
+      approval_count: u8::try_from(approval_count).map_err(|_| ErrorCode::MathOverflow)?,
+    });
+
+    Ok(())
+  }
+
+  pub fn execute_operation<'a>(
+    ctx: Context<'_, '_, '_, 'a, ExecuteOperationContext<'a>>,
+  ) -> Result<()> {
+
+    let timelock_operation = &ctx.accounts.timelock_operation;
+    if timelock_operation.is_executed || timelock_operation.is_cancelled {
+      return Err(ErrorCode::OperationFinalized.into());
+    }
+    let current_timestamp = get_current_timestamp()?;
+    if current_timestamp < timelock_operation.eta {
+      return Err(ErrorCode::TimelockNotExpired.into());
+    }
+    let admin_registry = &ctx.accounts.admin_registry;
+    if timelock_operation.approval_count(admin_registry) < usize::from(admin_registry.threshold) {
+      return Err(ErrorCode::ThresholdNotMet.into());
+    }
+
+    let operation = timelock_operation.operation.clone();
+    let root_signer = &ctx.accounts.root_signer;
+    let accounts = &ctx.remaining_accounts;
+    let seeds: &[&[u8]] = &[
+      ROOT_SIGNER_SEED_1,
+      ROOT_SIGNER_SEED_2,
+      &[ctx.accounts.app_data.signer_nonce],
+    ];
+
+    match operation {
+      Operation::WithdrawToken { pool_token, recipient_token, amount } => {
+        let pool_token_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+        let recipient_token_info = accounts.get(1).ok_or(ErrorCode::InvalidAccount)?;
+        if pool_token_info.key() != pool_token || recipient_token_info.key() != recipient_token {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+        let pool_token_account = unpack_token_account(pool_token_info)?;
+        if pool_token_account.owner != root_signer.key() {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+
+        transfer_token(
+            &*root_signer,
+            pool_token_info,
+            recipient_token_info,
+            amount,
+            &[&seeds],
+          )
+          .map_err(|_| ErrorCode::CpiFailed)?;
+
+        emit!(WithdrawTokenEvent {
+          recipient_token_account: recipient_token,
+          amount,
+        });
+      },
+      Operation::UnlockTokenMint { token_mint, new_authority } => {
+        let token_mint_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+        let new_authority_info = accounts.get(1).ok_or(ErrorCode::InvalidAccount)?;
+        if token_mint_info.key() != token_mint || new_authority_info.key() != new_authority {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+        let token_mint_account = TokenMint::unpack(&token_mint_info.try_borrow_data()?).map_err(|_| ErrorCode::InvalidAccount)?;
+        if !token_mint_account.mint_authority.contains(&root_signer.key()) {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+
+        transfer_authority(
+            &*root_signer,
+            token_mint_info,
+            0,
+            new_authority_info,
+            &[&seeds],
+          )
+          .map_err(|_| ErrorCode::CpiFailed)?;
+
+        emit!(UnlockTokenMintEvent {
+          token_mint,
+          new_authority,
+        });
+      },
+      Operation::SetMinterLimits { minter, total_minted_limit, per_period_minted_limit } => {
+        let minter_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+        if minter_info.key() != minter {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+        let mut minter_account = Account::<Minter>::try_from(minter_info)?;
+        minter_account.total_minted_limit = total_minted_limit;
+        minter_account.per_period_minted_limit = per_period_minted_limit;
+        minter_account.exit(ctx.program_id)?;
+      },
+      Operation::SetBurnerLimits { burner, total_burned_limit, per_period_burned_limit } => {
+        let burner_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+        if burner_info.key() != burner {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+        let mut burner_account = Account::<Burner>::try_from(burner_info)?;
+        burner_account.total_burned_limit = total_burned_limit;
+        burner_account.per_period_burned_limit = per_period_burned_limit;
+        burner_account.exit(ctx.program_id)?;
+      },
+      Operation::SetTimelockDelay { timelock_delay } => {
+        let app_data = &mut ctx.accounts.app_data;
+        app_data.timelock_delay = timelock_delay;
+      },
+      Operation::SetThreshold { threshold } => {
+        validate_threshold(&ctx.accounts.admin_registry, threshold)?;
+        let admin_registry = &mut ctx.accounts.admin_registry;
+        admin_registry.threshold = threshold;
+      },
+      Operation::SetTreasury { treasury } => {
+        let app_data = &mut ctx.accounts.app_data;
+        app_data.treasury = treasury;
+      },
+      Operation::GrantSigner { member } => {
+        let admin_registry = &mut ctx.accounts.admin_registry;
+        if admin_registry.has_role(Role::Signer, &member) {
+          return Err(ErrorCode::InvalidInput.into());
+        }
+        if admin_registry.signers.len() >= MAX_ROLE_MEMBER_COUNT {
+          return Err(ErrorCode::LimitReached.into());
+        }
+        admin_registry.signers.push(member);
+
+        emit!(GrantRoleEvent {
+          role: Role::Signer,
+          member,
+        });
+      },
+      Operation::SetLimit { limit } => {
+        let app_data = &mut ctx.accounts.app_data;
+        app_data.limit = limit;
+      },
+      Operation::SetMaxPriceAge { max_price_age } => {
+        let app_data = &mut ctx.accounts.app_data;
+        app_data.max_price_age = max_price_age;
+      },
+      Operation::SetMinter { minter, config } => {
+        let minter_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+        if minter_info.key() != minter {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+        validate_minter_config(&config)?;
+        let mut minter_account = Account::<Minter>::try_from(minter_info)?;
+        apply_minter_config(&mut minter_account, config);
+        minter_account.exit(ctx.program_id)?;
+      },
+      Operation::SetBurner { burner, config } => {
+        let burner_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+        if burner_info.key() != burner {
+          return Err(ErrorCode::InvalidAccount.into());
+        }
+        validate_burner_config(&config)?;
+        let mut burner_account = Account::<Burner>::try_from(burner_info)?;
+        apply_burner_config(&mut burner_account, config);
+        burner_account.exit(ctx.program_id)?;
+      },
+    }
+
+    let timelock_operation = &mut ctx.accounts.timelock_operation;
+    timelock_operation.is_executed = true;
+
+    emit!(ExecuteOperationEvent {
+      timelock_operation: timelock_operation.key(),
+      operation: timelock_operation.operation.clone(),
+    });
+
+    Ok(())
+  }
+
+  // remaining accounts are [minter, recipient CUSD account] to mint the CUSD fee
+  // or [burner, fee token account, recipient token account] to move the output token fee kept aside by burn
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Treasurer, *ctx.accounts.root.key))]
+  pub fn collect_fees<'a>(
+    ctx: Context<'_, '_, '_, 'a, CollectFeesContext<'a>>,
+  ) -> Result<()> {
+
+    let app_data = &ctx.accounts.app_data;
+    let root_signer = &ctx.accounts.root_signer;
+    let accounts = &ctx.remaining_accounts;
+    let seeds: &[&[u8]] = &[
+      ROOT_SIGNER_SEED_1,
+      ROOT_SIGNER_SEED_2,
+      &[app_data.signer_nonce],
+    ];
+
+    let fee_account_info = accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+    if let Ok(mut minter) = Account::<Minter>::try_from(fee_account_info) {
+      let cusd_mint = &ctx.accounts.cusd_mint;
+      let recipient_info = accounts.get(1).ok_or(ErrorCode::InvalidAccount)?;
+      let recipient = unpack_token_account(recipient_info)?;
+      if recipient.mint != cusd_mint.key() || recipient.owner != app_data.treasury {
+        return Err(ErrorCode::InvalidAccount.into());
+      }
+
+      let amount = minter.accumulated_fee;
+      minter.accumulated_fee = 0;
+      minter.exit(ctx.program_id)?;
+
+      mint_token(
+          &*root_signer,
+          &*cusd_mint,
+          recipient_info,
+          amount,
+          &[&seeds],
+        )
+        .map_err(|_| ErrorCode::CpiFailed)?;
+
+      emit!(CollectFeesEvent {
+        fee_account: fee_account_info.key(),
+        token: cusd_mint.key(),
+        recipient_token_account: recipient_info.key(),
+        amount,
+      });
+
+      return Ok(());
+    }
+
+    let mut burner = Account::<Burner>::try_from(fee_account_info)?;
+    let fee_signer = &ctx.accounts.fee_signer;
+    let fee_token_info = accounts.get(1).ok_or(ErrorCode::InvalidAccount)?;
+    let fee_token = unpack_token_account(fee_token_info)?;
+    if fee_token.mint != burner.output_token || fee_token.owner != fee_signer.key() {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+    let recipient_info = accounts.get(2).ok_or(ErrorCode::InvalidAccount)?;
+    let recipient = unpack_token_account(recipient_info)?;
+    if recipient.mint != burner.output_token || recipient.owner != app_data.treasury {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+
+    let amount = burner.accumulated_fee;
+    burner.accumulated_fee = 0;
+    burner.exit(ctx.program_id)?;
+
+    let fee_seeds: &[&[u8]] = &[
+      FEE_SIGNER_SEED_1,
+      FEE_SIGNER_SEED_2,
+      &[app_data.fee_signer_nonce],
+    ];
+    transfer_token(
+        &*fee_signer,
+        fee_token_info,
+        recipient_info,
+        amount,
+        &[&fee_seeds],
+      )
+      .map_err(|_| ErrorCode::CpiFailed)?;
+
+    emit!(CollectFeesEvent {
+      fee_account: fee_account_info.key(),
+      token: fee_token.mint,
+      recipient_token_account: recipient_info.key(),
+      amount,
+    });
+
+    Ok(())
+  }
+
+  #[access_control(has_role(&ctx.accounts.admin_registry, Role::Pauser, *ctx.accounts.root.key))]
+  pub fn cancel_operation(
+    ctx: Context<CancelOperationContext>,
+  ) -> Result<()> {
+
+    let timelock_operation = &mut ctx.accounts.timelock_operation;
+    if timelock_operation.is_executed || timelock_operation.is_cancelled {
+      return Err(ErrorCode::OperationFinalized.into());
+    }
+    timelock_operation.is_cancelled = true;
+
+    emit!(CancelOperationEvent {
+      timelock_operation: timelock_operation.key(),
+      operation: timelock_operation.operation.clone(),
+    });
+
+    Ok(())
+  }
+}
+
+pub fn is_root(user: Pubkey) -> Result<()> {
+  let user_key = user.to_string();
+  let result = ROOT_KEYS.iter().position(|&key| key == &user_key[..]);
+  if result == None {
+    return Err(ErrorCode::Unauthorized.into());
+  }
+
+  Ok(())
+}
+
+pub fn is_admin(admin_registry: &AdminRegistry, user: Pubkey) -> Result<()> {
+  if !admin_registry.is_admin(&user) {
+    return Err(ErrorCode::Unauthorized.into());
+  }
+
+  Ok(())
+}
+
+pub fn has_role(admin_registry: &AdminRegistry, role: Role, user: Pubkey) -> Result<()> {
+  if !admin_registry.has_role(role, &user) {
+    return Err(ErrorCode::Unauthorized.into());
+  }
+
+  Ok(())
+}
+
+// Approval threshold reachable by the current signers
+fn validate_threshold(admin_registry: &AdminRegistry, threshold: u8) -> Result<()> {
+  if threshold == 0 || usize::from(threshold) > admin_registry.signers.len() {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+
+  Ok(())
+}
+
+fn validate_minter_config(config: &MinterConfig) -> Result<()> {
+  let token_count = config.input_tokens.len();
+  if token_count > MAX_INPUT_TOKEN_COUNT {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if token_count != config.input_decimals.len() {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if token_count != config.input_percentages.len() {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if token_count != config.input_price_feeds.len() {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if token_count != config.input_price_configs.len() {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  for (price_config, price_feed) in config.input_price_configs.iter().zip(config.input_price_feeds.iter()) {
+    validate_price_config(price_config, price_feed)?;
+  }
+  let percentage: u16 = config.input_percentages.iter().sum();
+  if percentage != 10000 {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if config.fee_percent > SYSTEM_FEE_CAP {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+
+  Ok(())
+}
+
+// Raised limits and any change to collaterals, pricing or fees of a configured minter must be queued
+fn is_minter_loosened(minter: &Minter, config: &MinterConfig) -> bool {
+  if config.total_minted_limit > minter.total_minted_limit || config.per_period_minted_limit > minter.per_period_minted_limit {
+    return true;
+  }
+  if minter.input_tokens.is_empty() {
+    return false;
+  }
+  minter.input_tokens != config.input_tokens
+    || minter.input_decimals != config.input_decimals
+    || minter.input_percentages != config.input_percentages
+    || minter.input_price_feeds != config.input_price_feeds
+    || minter.input_price_configs != config.input_price_configs
+    || minter.fee_percent != config.fee_percent
+}
+
+// Reference prices are kept for the tokens still priced by the same feed
+fn apply_minter_config(minter: &mut Account<Minter>, config: MinterConfig) {
+  let input_last_prices = config.input_tokens.iter()
+    .zip(config.input_price_feeds.iter())
+    .map(|(token, price_feed)| {
+      minter.input_tokens.iter()
+        .zip(minter.input_price_feeds.iter())
+        .position(|(last_token, last_price_feed)| last_token == token && last_price_feed == price_feed)
+        .and_then(|j| minter.input_last_prices.get(j).copied())
+        .unwrap_or_default()
+    })
+    .collect();
+
+  minter.is_active = config.is_active;
+  minter.input_tokens = config.input_tokens.clone();
+  minter.input_decimals = config.input_decimals.clone();
+  minter.input_percentages = config.input_percentages.clone();
+  minter.input_price_feeds = config.input_price_feeds.clone();
+  minter.input_price_configs = config.input_price_configs.clone();
+  minter.input_last_prices = input_last_prices;
+  minter.fee_percent = config.fee_percent;
+  minter.total_minted_limit = config.total_minted_limit;
+  minter.per_period_minted_limit = config.per_period_minted_limit;
+  minter.min_amount = config.min_amount;
+
+  emit!(SetMinterEvent {
+    is_active: config.is_active,
+    input_tokens: config.input_tokens,
+    input_decimals: config.input_decimals,
+    input_percentages: config.input_percentages,
+    input_price_feeds: config.input_price_feeds,
+    input_price_configs: config.input_price_configs,
+    fee_percent: config.fee_percent,
+    total_minted_limit: config.total_minted_limit,
+    per_period_minted_limit: config.per_period_minted_limit,
+    min_amount: config.min_amount,
+  });
+}
+
+fn validate_burner_config(config: &BurnerConfig) -> Result<()> {
+  if config.fee_percent > SYSTEM_FEE_CAP {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  validate_price_config(&config.output_price_config, &config.output_price_feed)?;
+
+  Ok(())
+}
+
+// Raised limits and any change to the output token, pricing or fees of a configured burner must be queued
+fn is_burner_loosened(burner: &Burner, config: &BurnerConfig) -> bool {
+  if config.total_burned_limit > burner.total_burned_limit || config.per_period_burned_limit > burner.per_period_burned_limit {
+    return true;
+  }
+  if burner.output_token == Pubkey::default() {
+    return false;
+  }
+  burner.output_token != config.output_token
+    || burner.output_decimals != config.output_decimals
+    || burner.output_price_feed != config.output_price_feed
+    || burner.output_price_config != config.output_price_config
+    || burner.fee_percent != config.fee_percent
+}
+
+// The reference price is kept while the output token is priced by the same feed
+fn apply_burner_config(burner: &mut Account<Burner>, config: BurnerConfig) {
+  if burner.output_token != config.output_token || burner.output_price_feed != config.output_price_feed {
+    burner.output_last_price = AcceptedPrice::default();
+  }
+  burner.is_active = config.is_active;
+  burner.output_token = config.output_token;
+  burner.output_decimals = config.output_decimals;
+  burner.output_price_feed = config.output_price_feed;
+  burner.output_price_config = config.output_price_config.clone();
+  burner.fee_percent = config.fee_percent;
+  burner.total_burned_limit = config.total_burned_limit;
+  burner.per_period_burned_limit = config.per_period_burned_limit;
+  burner.min_amount = config.min_amount;
+
+  emit!(SetBurnerEvent {
+    is_active: config.is_active,
+    output_token: config.output_token,
+    output_decimals: config.output_decimals,
+    output_price_feed: config.output_price_feed,
+    output_price_config: config.output_price_config,
+    fee_percent: config.fee_percent,
+    total_burned_limit: config.total_burned_limit,
+    per_period_burned_limit: config.per_period_burned_limit,
+    min_amount: config.min_amount,
+  });
+}
+
+// Legacy accounts priced system_program feeds 1:1 with CUSD and every other feed through Chainlink
+fn legacy_price_config(price_feed: &Pubkey) -> PriceConfig {
+  let oracle_type = if *price_feed == system_program::ID {
+    OracleType::Fixed
+  } else {
+    OracleType::Chainlink
+  };
+
+  PriceConfig {
+    oracle_type,
+    ..PriceConfig::default()
+  }
+}
+
+// Tops up rent from payer before growing the account, used to migrate accounts to a larger layout
+fn realloc_account<'a>(
+  payer: &AccountInfo<'a>,
+  account: &AccountInfo<'a>,
+  system_program: &AccountInfo<'a>,
+  new_len: usize,
+) -> Result<()> {
+  let rent = Rent::get()?;
+  let required_lamports = rent.minimum_balance(new_len).saturating_sub(account.lamports());
+  if required_lamports > 0 {
+    invoke(
+        &system_instruction::transfer(payer.key, account.key, required_lamports),
+        &[payer.clone(), account.clone(), system_program.clone()],
+      )
+      .map_err(|_| ErrorCode::CpiFailed)?;
+  }
+  account.realloc(new_len, true)?;
+
+  Ok(())
+}
+
+// remaining accounts follow the layout of mint, the TWAP is only written when update_twap is set
+fn get_input_price<'a>(
+  app_data: &AppData,
+  minter: &Minter,
+  chainlink_program: &AccountInfo<'a>,
+  remaining_accounts: &[AccountInfo<'a>],
+  index: usize,
+  update_twap: bool,
+) -> Result<OraclePrice> {
+
+  let price_config = &minter.input_price_configs[index];
+
+  // extra feeds of every input token follow the 3 accounts per token, in token order
+  let extra_offset = 3 * minter.input_tokens.len() + minter.input_price_configs[..index].iter()
+    .map(|config| config.extra_price_feeds.len())
+    .sum::<usize>();
+  let extra_price_feeds = remaining_accounts
+    .get(extra_offset..extra_offset + price_config.extra_price_feeds.len())
+    .ok_or(ErrorCode::InvalidAccount)?;
+
+  let price_feed = remaining_accounts.get(3*index).ok_or(ErrorCode::InvalidAccount)?;
+  let price = get_price(
+    app_data,
+    chainlink_program,
+    price_feed,
+    &minter.input_price_feeds[index],
+    extra_price_feeds,
+    price_config,
+  )?;
+  if price_config.price_mode == PriceMode::Spot {
+    return Ok(price);
+  }
+
+  // TWAP accounts of tokens not priced at spot follow all the extra feeds, in token order
+  let twap_offset = 3 * minter.input_tokens.len() + minter.input_price_configs.iter()
+    .map(|config| config.extra_price_feeds.len())
+    .sum::<usize>() + minter.input_price_configs[..index].iter()
+    .filter(|config| config.price_mode != PriceMode::Spot)
+    .count();
+  let twap = remaining_accounts.get(twap_offset).ok_or(ErrorCode::InvalidAccount)?;
+  let twap = load_twap(app_data, chainlink_program, twap, price_feed, extra_price_feeds, update_twap)?;
+
+  apply_twap(&twap, price_config.price_mode, price, true)
+}
+
+fn transfer_input_token<'a>(
+  ctx: &Context<'_, '_, '_, 'a, MintContext<'a>>,
+  account_indices: &Vec<usize>,
+  index: usize,
+  input_amount: u64,
+) -> Result<()> {
+
+  let user = &ctx.accounts.user;
+  let root_signer = &ctx.accounts.root_signer;
+  let input_token = &ctx.accounts.minter.input_tokens[index];
+  let accounts = &ctx.remaining_accounts;
+
+  let from_account_info = account_indices.get(3*index + 1)
+    .and_then(|account_index| accounts.get(*account_index))
+    .ok_or(ErrorCode::InvalidAccount)?;
+  let to_account_info = account_indices.get(3*index + 2)
+    .and_then(|account_index| accounts.get(*account_index))
+    .ok_or(ErrorCode::InvalidAccount)?;
+  let from_account = unpack_token_account(from_account_info)?;
+  let to_account = unpack_token_account(to_account_info)?;
+  if from_account.mint != *input_token {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+  if to_account.mint != *input_token || to_account.owner != root_signer.key() {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+
+  transfer_token(
+      &*user,
+      from_account_info,
+      to_account_info,
+      input_amount,
+      &[],
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
+
+  Ok(())
+}
+
+// amount is the value of the collateral in CUSD, protocol fee is deducted from it
+// period is the result of check_mint_limits, run before any fund moved
+fn process_mint<'a>(
+  ctx: Context<'_, '_, '_, 'a, MintContext<'a>>,
+  amount: u64,
+  input_amounts: Vec<u64>,
+  prices: &[OraclePrice],
+  period: (bool, u64),
+  current_timestamp: i64,
+) -> Result<()> {
+
+  let app_data = &ctx.accounts.app_data;
+  let root_signer = &ctx.accounts.root_signer;
+  let (is_in_period, current_period_minted_amount) = period;
+
+  let minter = &mut ctx.accounts.minter;
+  minter.total_minted_amount = checked_add(minter.total_minted_amount, amount)?;
+  minter.per_period_minted_amount = checked_add(current_period_minted_amount, amount)?;
+  if !is_in_period {
+    minter.last_period_timestamp = current_timestamp;
+  }
+
+  let protocol_fee = checked_mul_div(amount, u64::from(minter.fee_percent), 10000, Rounding::Ceil)?;
+  let amount_to_transfer = checked_sub(amount, protocol_fee)?;
+  minter.accumulated_fee = checked_add(minter.accumulated_fee, protocol_fee)?;
+
+  let cusd_mint = &ctx.accounts.cusd_mint;
+  let recipient = &ctx.accounts.recipient;
+
+  let seeds: &[&[u8]] = &[
+    ROOT_SIGNER_SEED_1,
+    ROOT_SIGNER_SEED_2,
+    &[app_data.signer_nonce],
+  ];
+
+  mint_token(
+      &*root_signer,
+      &*cusd_mint,
+      &*recipient,
+      amount_to_transfer,
+      &[&seeds],
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
+
+  let minter = &ctx.accounts.minter;
+  emit!(MintEvent {
+    user: ctx.accounts.user.key(),
+    minter: minter.key(),
+    recipient: recipient.key(),
+    amount,
+    fee: protocol_fee,
+    input_amounts: input_amounts.clone(),
+    input_prices: prices.iter().map(|price| price.min_value()).collect(),
+    input_precisions: prices.iter().map(|price| price.precision()).collect(),
+    total_minted_amount: minter.total_minted_amount,
+    per_period_minted_amount: minter.per_period_minted_amount,
+    last_period_timestamp: minter.last_period_timestamp,
+  });
+
+  let result = MintResult {
+    input_amounts,
+    input_prices: prices.iter().map(|price| price.min_value()).collect(),
+    input_precisions: prices.iter().map(|price| price.precision()).collect(),
+    amount_out: amount_to_transfer,
+    fee: protocol_fee,
+  };
+  set_result(&result)?;
+
+  Ok(())
+}
+
+// Returns whether the current period is still running and the amount already minted in it
+fn check_mint_limits(app_data: &AppData, minter: &Minter, amount: u64, current_timestamp: i64) -> Result<(bool, u64)> {
+  if app_data.is_paused || app_data.is_mint_paused {
+    return Err(ErrorCode::Paused.into());
+  }
+  if amount < minter.min_amount {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if !minter.is_active {
+    return Err(ErrorCode::Unavailable.into());
+  }
+
+  let is_in_period = period_end(minter.last_period_timestamp, app_data.limit)? > current_timestamp;
+  let current_period_minted_amount = if is_in_period { minter.per_period_minted_amount } else { 0u64 };
+
+  if checked_add(current_period_minted_amount, amount)? > minter.per_period_minted_limit {
+    return Err(ErrorCode::LimitReached.into());
+  }
+  if checked_add(minter.total_minted_amount, amount)? > minter.total_minted_limit {
+    return Err(ErrorCode::LimitReached.into());
+  }
+
+  Ok((is_in_period, current_period_minted_amount))
+}
+
+// Returns whether the current period is still running and the CUSD amount already burned in it
+fn check_burn_limits(app_data: &AppData, burner: &Burner, amount: u64, cusd_amount: u64, current_timestamp: i64) -> Result<(bool, u64)> {
+  if app_data.is_paused || app_data.is_burn_paused {
+    return Err(ErrorCode::Paused.into());
+  }
+  if amount < burner.min_amount {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if !burner.is_active {
+    return Err(ErrorCode::Unavailable.into());
+  }
+
+  let is_in_period = period_end(burner.last_period_timestamp, app_data.limit)? > current_timestamp;
+  let current_period_burned_amount = if is_in_period { burner.per_period_burned_amount } else { 0u64 };
+
+  if checked_add(current_period_burned_amount, cusd_amount)? > burner.per_period_burned_limit {
+    return Err(ErrorCode::LimitReached.into());
+  }
+  if checked_add(burner.total_burned_amount, cusd_amount)? > burner.total_burned_limit {
+    return Err(ErrorCode::LimitReached.into());
+  }
+
+  Ok((is_in_period, current_period_burned_amount))
+}
+
+// Amount of each input token worth its share of amount CUSD
+fn compute_input_amounts(minter: &Minter, amount: u64, prices: &[OraclePrice]) -> Result<Vec<u64>> {
+  let mut input_amounts: Vec<u64> = Vec::new();
+  for i in 0..minter.input_tokens.len() {
+    let price = prices[i];
+
+    let value_contrib = minter.input_percentages[i];
+
+    let input_value = checked_mul_div(amount, u64::from(value_contrib), 10000, Rounding::Ceil)?;
+    let input_amount = checked_mul_div(input_value, price.precision(), price.min_value(), Rounding::Ceil)?;
+    let input_precision = checked_pow10(u32::from(minter.input_decimals[i]))?;
+    let input_amount = checked_mul_div(input_amount, input_precision, CUSD_PRECISION, Rounding::Ceil)?;
+    input_amounts.push(input_amount);
+  }
+
+  Ok(input_amounts)
+}
+
+// CUSD value of amount output_token
+fn compute_burn_cusd_amount(burner: &Burner, amount: u64, price: &OraclePrice) -> Result<u64> {
+  let cusd_amount = checked_mul_div(amount, price.max_value()?, price.precision(), Rounding::Ceil)?;
+  let output_precision = checked_pow10(u32::from(burner.output_decimals))?;
+  checked_mul_div(cusd_amount, CUSD_PRECISION, output_precision, Rounding::Ceil)
+}
+
+// remaining accounts follow the layout of burn, the TWAP is only written when update_twap is set
+fn get_output_price<'a>(
+  app_data: &AppData,
+  burner: &Burner,
+  chainlink_program: &AccountInfo<'a>,
+  remaining_accounts: &[AccountInfo<'a>],
+  update_twap: bool,
+) -> Result<OraclePrice> {
+
+  let price_config = &burner.output_price_config;
+
+  // extra feeds follow the price feed, pool token and user token
+  let extra_price_feeds = remaining_accounts
+    .get(3..3 + price_config.extra_price_feeds.len())
+    .ok_or(ErrorCode::InvalidAccount)?;
+
+  let price_feed = remaining_accounts.get(0).ok_or(ErrorCode::InvalidAccount)?;
+  let price = get_price(
+    app_data,
+    chainlink_program,
+    price_feed,
+    &burner.output_price_feed,
+    extra_price_feeds,
+    price_config,
+  )?;
+  if price_config.price_mode == PriceMode::Spot {
+    return Ok(price);
+  }
+
+  // TWAP account follows the extra feeds
+  let twap = remaining_accounts.get(3 + price_config.extra_price_feeds.len()).ok_or(ErrorCode::InvalidAccount)?;
+  let twap = load_twap(app_data, chainlink_program, twap, price_feed, extra_price_feeds, update_twap)?;
+
+  apply_twap(&twap, price_config.price_mode, price, false)
+}
+
+// Loads the TWAP of the feed with the current price recorded, the same way poke does, the record is
+// only written back when update is set so quotes see the TWAP mint and burn would use
+fn load_twap<'a>(
+  app_data: &AppData,
+  chainlink_program: &AccountInfo<'a>,
+  twap_info: &AccountInfo<'a>,
+  price_feed: &AccountInfo<'a>,
+  extra_price_feeds: &[AccountInfo<'a>],
+  update: bool,
+) -> Result<Account<'a, Twap>> {
+
+  if update && !twap_info.is_writable {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+  let mut twap = Account::<Twap>::try_from(twap_info)?;
+  record_twap_price(app_data, chainlink_program, price_feed, extra_price_feeds, &mut twap)?;
+  if update {
+    twap.exit(&crate::ID)?;
+  }
+
+  Ok(twap)
+}
+
+// Records the price of the feed validated against the TWAP price config, extra price feeds are
+// checked against the ones of that config, in order
+fn record_twap_price<'a>(
+  app_data: &AppData,
+  chainlink_program: &AccountInfo<'a>,
+  price_feed: &AccountInfo<'a>,
+  extra_price_feeds: &[AccountInfo<'a>],
+  twap: &mut Twap,
+) -> Result<()> {
+
+  let price = get_price(
+    app_data,
+    chainlink_program,
+    price_feed,
+    &twap.price_feed,
+    extra_price_feeds,
+    &twap.price_config,
+  )?;
+
+  let current_timestamp = get_current_timestamp()?;
+  twap.update(price.rescale(TWAP_EXPONENT)?.value, current_timestamp)
+}
+
+// Returns the price picked by the price mode from the spot price and the TWAP of the feed,
+// the less favourable price is the lower one for collateral paid in and the higher one otherwise
+fn apply_twap(
+  twap: &Twap,
+  price_mode: PriceMode,
+  spot_price: OraclePrice,
+  is_collateral_in: bool,
+) -> Result<OraclePrice> {
+
+  let spot_price = spot_price.rescale(TWAP_EXPONENT)?;
+
+  let twap_price = OraclePrice {
+    value: twap.average()?,
+    ..spot_price
+  };
+  if twap_price.confidence >= twap_price.value {
+    return Err(ErrorCode::ConfidenceTooWide.into());
+  }
+
+  let price = match price_mode {
+    PriceMode::Spot => spot_price,
+    PriceMode::Twap => twap_price,
+    PriceMode::LessFavourable if is_collateral_in => {
+      if twap_price.value < spot_price.value { twap_price } else { spot_price }
+    },
+    PriceMode::LessFavourable => {
+      if twap_price.value > spot_price.value { twap_price } else { spot_price }
+    },
+  };
+
+  Ok(price)
+}
+
+fn get_price<'a>(
+  app_data: &AppData,
+  chainlink_program: &AccountInfo<'a>,
+  price_feed: &AccountInfo<'a>,
+  expected_price_feed: &Pubkey,
+  extra_price_feeds: &[AccountInfo<'a>],
+  price_config: &PriceConfig,
+) -> Result<OraclePrice> {
+
+  if price_config.oracle_type != OracleType::Fixed && price_feed.key() != *expected_price_feed {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+  for (i, extra_price_feed) in price_config.extra_price_feeds.iter().enumerate() {
+    let extra_price_feed_info = extra_price_feeds.get(i).ok_or(ErrorCode::InvalidAccount)?;
+    if extra_price_feed_info.key() != extra_price_feed.address {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+  }
+
+  let extra_sources: Vec<(OracleType, &AccountInfo<'a>)> = price_config.extra_price_feeds.iter()
+    .map(|feed| feed.oracle_type)
+    .zip(extra_price_feeds.iter())
+    .collect();
+
+  // pegged collaterals are priced 1:1, their extra feeds only serve as reference to detect a depeg
+  if price_config.oracle_type == OracleType::Fixed {
+    let price = get_oracle_price(OracleType::Fixed, chainlink_program, price_feed)?;
+    if !extra_sources.is_empty() {
+      let reference_price = aggregate_prices(app_data, chainlink_program, extra_sources, price_config)?;
+      check_peg(price_config, &reference_price)?;
+    }
+    check_price_bounds(price_config, price.value, price.precision())?;
+
+    return Ok(price);
+  }
+
+  // the primary feed is aggregated like the extra feeds, a broken or stale primary is left out
+  // as long as the fresh feeds still meet the quorum
+  let mut sources = Vec::from([(price_config.oracle_type, price_feed)]);
+  sources.extend(extra_sources);
+  let price = aggregate_prices(app_data, chainlink_program, sources, price_config)?;
+  check_price_bounds(price_config, price.value, price.precision())?;
+  check_price_confidence(price_config, &price)?;
+
+  Ok(price)
+}
+
+// Median of the fresh feeds, a feed that cannot be read or is stale is left out
+// and the last failure is reported if no price is usable
+fn aggregate_prices<'a>(
+  app_data: &AppData,
+  chainlink_program: &AccountInfo<'a>,
+  sources: Vec<(OracleType, &AccountInfo<'a>)>,
+  price_config: &PriceConfig,
+) -> Result<OraclePrice> {
+
+  let mut prices: Vec<OraclePrice> = Vec::new();
+  let mut last_error: Option<Error> = None;
+  for (oracle_type, account) in sources {
+    let result = get_oracle_price(oracle_type, chainlink_program, account)
+      .and_then(|price| {
+        check_price_age(app_data, price.timestamp)?;
+        Ok(price)
+      });
+    match result {
+      Ok(price) => prices.push(price),
+      Err(error) => {
+        msg!("Price feed {} skipped: {}", account.key(), error);
+        last_error = Some(error);
+      },
+    }
+  }
+  if prices.is_empty() {
+    return Err(last_error.unwrap_or_else(|| ErrorCode::QuorumNotMet.into()));
+  }
+  if prices.len() < usize::from(price_config.quorum) {
+    return Err(ErrorCode::QuorumNotMet.into());
+  }
+  if price_config.max_divergence > 0 && price_divergence(&prices)? > u64::from(price_config.max_divergence) {
+    return Err(ErrorCode::PriceDivergence.into());
+  }
+
+  median_price(&prices)
+}
+
+fn check_price_confidence(price_config: &PriceConfig, price: &OraclePrice) -> Result<()> {
+  // mint values collateral at price minus confidence so it must stay positive
+  if price.confidence >= price.value {
+    return Err(ErrorCode::ConfidenceTooWide.into());
+  }
+  if price_config.max_confidence_ratio > 0 && checked_mul_div(price.confidence, 10000, price.value, Rounding::Floor)? > u64::from(price_config.max_confidence_ratio) {
+    return Err(ErrorCode::ConfidenceTooWide.into());
+  }
+
+  Ok(())
+}
+
+fn check_peg(price_config: &PriceConfig, reference_price: &OraclePrice) -> Result<()> {
+  let cusd_price = checked_mul_div(reference_price.value, CUSD_PRECISION, reference_price.precision(), Rounding::Floor)?;
+  let difference = if cusd_price > CUSD_PRECISION { cusd_price - CUSD_PRECISION } else { CUSD_PRECISION - cusd_price };
+  if checked_mul_div(difference, 10000, CUSD_PRECISION, Rounding::Floor)? > u64::from(price_config.max_peg_deviation) {
+    return Err(ErrorCode::Depegged.into());
+  }
+
+  Ok(())
+}
+
+// amount is denominated in output_token, cusd_amount is its value in CUSD
+// period is the result of check_burn_limits, run before any fund moved
+fn process_burn<'a>(
+  ctx: Context<'_, '_, '_, 'a, BurnContext<'a>>,
+  amount: u64,
+  cusd_amount: u64,
+  price: &OraclePrice,
+  period: (bool, u64),
+  current_timestamp: i64,
+) -> Result<()> {
+
+  let user = &ctx.accounts.user;
+  let app_data = &ctx.accounts.app_data;
+  let accounts = &ctx.remaining_accounts;
+  let (is_in_period, current_period_burned_amount) = period;
+
+  let pool_cusd = &ctx.accounts.pool_cusd;
+  let user_cusd = &ctx.accounts.user_cusd;
+  transfer_token(
+      &*user,
+      &user_cusd.to_account_info(),
+      &pool_cusd.to_account_info(),
+      cusd_amount,
+      &[],
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
+
+  let root_signer = &ctx.accounts.root_signer;
+  let cusd_mint = &ctx.accounts.cusd_mint;
+  let seeds: &[&[u8]] = &[
+    ROOT_SIGNER_SEED_1,
+    ROOT_SIGNER_SEED_2,
+    &[app_data.signer_nonce],
+  ];
+  burn_token(
+      &*root_signer,
+      &*cusd_mint,
+      &pool_cusd.to_account_info(),
+      cusd_amount,
+      &[&seeds],
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
+
+  let burner = &mut ctx.accounts.burner;
+  burner.total_burned_amount = checked_add(burner.total_burned_amount, cusd_amount)?;
+  burner.per_period_burned_amount = checked_add(current_period_burned_amount, cusd_amount)?;
+  if !is_in_period {
+    burner.last_period_timestamp = current_timestamp;
+  }
+  let protocol_fee = checked_mul_div(amount, u64::from(burner.fee_percent), 10000, Rounding::Ceil)?;
+  let amount_to_transfer = checked_sub(amount, protocol_fee)?;
+  burner.accumulated_fee = checked_add(burner.accumulated_fee, protocol_fee)?;
+
+  let pool_token_info = accounts.get(1).ok_or(ErrorCode::InvalidAccount)?;
+  let pool_token = unpack_token_account(pool_token_info)?;
+  if pool_token.owner != root_signer.key() || pool_token.mint != burner.output_token {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+  let user_token_info = accounts.get(2).ok_or(ErrorCode::InvalidAccount)?;
+  let user_token = unpack_token_account(user_token_info)?;
+  if user_token.mint != burner.output_token {
+    return Err(ErrorCode::InvalidAccount.into());
+  }
+  transfer_token(
+      &*root_signer,
+      pool_token_info,
+      user_token_info,
+      amount_to_transfer,
+      &[&seeds],
+    )
+    .map_err(|_| ErrorCode::CpiFailed)?;
+
+  // the fee leaves the pool right away so reserve withdrawals cannot take it,
+  // the fee token account follows the TWAP account, or the extra feeds when priced at spot
+  if protocol_fee > 0 {
+    let price_config = &burner.output_price_config;
+    let fee_index = 3 + price_config.extra_price_feeds.len() + if price_config.price_mode == PriceMode::Spot { 0 } else { 1 };
+    let fee_token_info = accounts.get(fee_index).ok_or(ErrorCode::InvalidAccount)?;
+    let fee_token = unpack_token_account(fee_token_info)?;
+    let fee_signer = Pubkey::create_program_address(
+        &[
+          FEE_SIGNER_SEED_1,
+          FEE_SIGNER_SEED_2,
+          &[app_data.fee_signer_nonce],
+        ],
+        ctx.program_id,
+      )
+      .map_err(|_| ErrorCode::InvalidAccount)?;
+    if fee_token.owner != fee_signer || fee_token.mint != burner.output_token {
+      return Err(ErrorCode::InvalidAccount.into());
+    }
+    transfer_token(
+        &*root_signer,
+        pool_token_info,
+        fee_token_info,
+        protocol_fee,
+        &[&seeds],
+      )
+      .map_err(|_| ErrorCode::CpiFailed)?;
+  }
+
+  emit!(BurnEvent {
+    user: user.key(),
+    burner: burner.key(),
+    recipient: user_token_info.key(),
+    cusd_amount,
+    output_amount: amount,
+    output_price: price.max_value()?,
+    output_precision: price.precision(),
+    fee: protocol_fee,
+    total_burned_amount: burner.total_burned_amount,
+    per_period_burned_amount: burner.per_period_burned_amount,
+    last_period_timestamp: burner.last_period_timestamp,
+  });
+
+  let result = BurnResult {
+    cusd_amount_in: cusd_amount,
+    amount_out: amount_to_transfer,
+    output_price: price.max_value()?,
+    output_precision: price.precision(),
+    fee: protocol_fee,
+  };
+  set_result(&result)?;
+
+  Ok(())
+}
+
+fn set_result<T: AnchorSerialize>(result: &T) -> Result<()> {
+  let data = result.try_to_vec().map_err(|_| ErrorCode::InvalidInput)?;
+  set_return_data(&data);
+
+  Ok(())
+}
+
+// Deviation in basis points from the last accepted price when it exceeds max_deviation within the window
+fn price_deviation(price_config: &PriceConfig, last_price: &AcceptedPrice, price: u64, current_timestamp: i64) -> Option<u64> {
+  if price_config.max_deviation == 0 || last_price.price == 0 {
+    return None;
+  }
+  if current_timestamp - last_price.timestamp > i64::from(price_config.deviation_window) {
+    return None;
+  }
+
+  let difference = if price > last_price.price { price - last_price.price } else { last_price.price - price };
+  let deviation = u128::from(difference) * 10000 / u128::from(last_price.price);
+  let deviation = u64::try_from(deviation).unwrap_or(u64::MAX);
+  if deviation > u64::from(price_config.max_deviation) {
+    return Some(deviation);
+  }
+
+  None
+}
+
+// Fails when a price deviates from the reference price of its window, with CircuitBreakerTripped when
+// the minter should be paused through trip_minter_circuit_breaker, shared by mint and quote_mint
+fn check_minter_circuit_breaker(minter: &Minter, prices: &[OraclePrice], current_timestamp: i64) -> Result<()> {
+  for (i, price) in prices.iter().enumerate() {
+    let cusd_price = checked_mul_div(price.value, CUSD_PRECISION, price.precision(), Rounding::Floor)?;
+    let price_config = &minter.input_price_configs[i];
+    if price_deviation(price_config, &minter.input_last_prices[i], cusd_price, current_timestamp).is_some() {
+      return Err(deviation_error(price_config));
+    }
+  }
+
+  Ok(())
+}
+
+// The reference is only renewed once its window is over so a price cannot be walked away from it in small steps
+fn update_minter_reference_prices(minter: &mut Minter, prices: &[OraclePrice], current_timestamp: i64) -> Result<()> {
+  for (i, price) in prices.iter().enumerate() {
+    let cusd_price = checked_mul_div(price.value, CUSD_PRECISION, price.precision(), Rounding::Floor)?;
+    minter.input_last_prices[i] = next_reference_price(&minter.input_price_configs[i], &minter.input_last_prices[i], cusd_price, current_timestamp);
+  }
+
+  Ok(())
+}
+
+// Same as check_minter_circuit_breaker for the output token of a burner
+fn check_burner_circuit_breaker(burner: &Burner, price: &OraclePrice, current_timestamp: i64) -> Result<()> {
+  let cusd_price = checked_mul_div(price.value, CUSD_PRECISION, price.precision(), Rounding::Floor)?;
+
+  let price_config = &burner.output_price_config;
+  if price_deviation(price_config, &burner.output_last_price, cusd_price, current_timestamp).is_some() {
+    return Err(deviation_error(price_config));
+  }
+
+  Ok(())
+}
+
+// Same as update_minter_reference_prices for the output token of a burner
+fn update_burner_reference_price(burner: &mut Burner, price: &OraclePrice, current_timestamp: i64) -> Result<()> {
+  let cusd_price = checked_mul_div(price.value, CUSD_PRECISION, price.precision(), Rounding::Floor)?;
+  burner.output_last_price = next_reference_price(&burner.output_price_config, &burner.output_last_price, cusd_price, current_timestamp);
+
+  Ok(())
+}
+
+fn deviation_error(price_config: &PriceConfig) -> Error {
+  if price_config.pause_on_deviation {
+    ErrorCode::CircuitBreakerTripped.into()
+  } else {
+    ErrorCode::PriceDeviation.into()
+  }
+}
+
+fn next_reference_price(price_config: &PriceConfig, last_price: &AcceptedPrice, price: u64, current_timestamp: i64) -> AcceptedPrice {
+  if last_price.price != 0 && current_timestamp - last_price.timestamp <= i64::from(price_config.deviation_window) {
+    return *last_price;
+  }
+
+  AcceptedPrice {
+    price,
+    timestamp: current_timestamp,
+  }
+}
+
+fn check_price_age(app_data: &AppData, updated_at: i64) -> Result<()> {
+  let current_timestamp = get_current_timestamp()?;
+  if current_timestamp - updated_at > i64::from(app_data.max_price_age) {
+    return Err(ErrorCode::StalePrice.into());
+  }
+
+  Ok(())
+}
+
+fn check_price_bounds(price_config: &PriceConfig, price: u64, precision: u64) -> Result<()> {
+  let cusd_price = checked_mul_div(price, CUSD_PRECISION, precision, Rounding::Floor)?;
+  if price_config.min_price > 0 && cusd_price < price_config.min_price {
+    return Err(ErrorCode::PriceOutOfBounds.into());
+  }
+  if price_config.max_price > 0 && cusd_price > price_config.max_price {
+    return Err(ErrorCode::PriceOutOfBounds.into());
+  }
+
+  Ok(())
+}
+
+fn validate_price_config(price_config: &PriceConfig, price_feed: &Pubkey) -> Result<()> {
+  if (price_config.oracle_type == OracleType::Fixed) != (*price_feed == system_program::ID) {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if price_config.extra_price_feeds.len() > MAX_EXTRA_PRICE_FEED_COUNT {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  // a pegged collateral takes reference feeds only together with a peg band
+  if price_config.oracle_type == OracleType::Fixed && price_config.extra_price_feeds.is_empty() != (price_config.max_peg_deviation == 0) {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if price_config.oracle_type != OracleType::Fixed && price_config.max_peg_deviation > 0 {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if price_config.oracle_type == OracleType::Fixed && price_config.price_mode != PriceMode::Spot {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  for extra_price_feed in price_config.extra_price_feeds.iter() {
+    if extra_price_feed.oracle_type == OracleType::Fixed || extra_price_feed.address == system_program::ID {
+      return Err(ErrorCode::InvalidInput.into());
+    }
+  }
+  let primary_feed_count = if price_config.oracle_type == OracleType::Fixed { 0 } else { 1 };
+  let feed_count = primary_feed_count + price_config.extra_price_feeds.len();
+  if usize::from(price_config.quorum) > feed_count {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  // the primary feed may be left out, extra feeds alone must not be able to set the price
+  if price_config.oracle_type != OracleType::Fixed && !price_config.extra_price_feeds.is_empty() && 2 * usize::from(price_config.quorum) <= feed_count {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+  if price_config.min_price > 0 && price_config.max_price > 0 && price_config.min_price > price_config.max_price {
+    return Err(ErrorCode::InvalidInput.into());
+  }
+
+  Ok(())
+}
+
+fn get_current_timestamp() -> Result<i64> {
+  let clock = Clock::get().map_err(|_| ErrorCode::ClockUnavailable)?;
+  Ok(clock.unix_timestamp)
+}
+
+// Initialized token account owned by the token program
+fn unpack_token_account(account: &AccountInfo) -> Result<TokenAccount> {
+  if *account.owner != spl_token::ID {
+    return Err(ErrorCode::InvalidTokenAccount.into());
+  }
+  let data = account.try_borrow_data().map_err(|_| ErrorCode::InvalidTokenAccount)?;
+  let token_account = TokenAccount::unpack(&data).map_err(|_| ErrorCode::InvalidTokenAccount)?;
+  Ok(token_account)
 }
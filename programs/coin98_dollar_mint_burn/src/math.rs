@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::error::{
+  ErrorCode,
+};
+
+pub const SECONDS_PER_HOUR: i64 = 3600;
+
+// Amounts charged to the user round up and amounts paid out round down, prices round down
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+  Floor,
+  Ceil,
+}
+
+pub fn checked_add(a: u64, b: u64) -> Result<u64> {
+  let result = a.checked_add(b).ok_or(ErrorCode::MathOverflow)?;
+  Ok(result)
+}
+
+pub fn checked_sub(a: u64, b: u64) -> Result<u64> {
+  let result = a.checked_sub(b).ok_or(ErrorCode::MathOverflow)?;
+  Ok(result)
+}
+
+// number * numerator / denominator computed on 128 bits, fails on a zero denominator or a result above u64
+pub fn checked_mul_div(number: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+  if denominator == 0 {
+    return Err(ErrorCode::MathOverflow.into());
+  }
+  let product = u128::from(number) * u128::from(numerator);
+  let denominator = u128::from(denominator);
+  let quotient = product / denominator;
+  let quotient = if rounding == Rounding::Ceil && product % denominator != 0 { quotient + 1 } else { quotient };
+
+  let result = u64::try_from(quotient).map_err(|_| ErrorCode::MathOverflow)?;
+  Ok(result)
+}
+
+// Denominator of an amount with exponent decimals
+pub fn checked_pow10(exponent: u32) -> Result<u64> {
+  let result = u64::checked_pow(10, exponent).ok_or(ErrorCode::MathOverflow)?;
+  Ok(result)
+}
+
+// Timestamp at which a limit period of hours started at start_timestamp ends
+pub fn period_end(start_timestamp: i64, hours: u32) -> Result<i64> {
+  let result = i64::from(hours)
+    .checked_mul(SECONDS_PER_HOUR)
+    .and_then(|duration| start_timestamp.checked_add(duration))
+    .ok_or(ErrorCode::MathOverflow)?;
+  Ok(result)
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use solana_program::program::{
+  get_return_data,
+};
+
+// Set as return data by mint and mint_exact_input, prices are the ones applied
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MintResult {
+  pub input_amounts: Vec<u64>,
+  pub input_prices: Vec<u64>,
+  pub input_precisions: Vec<u64>,
+  pub amount_out: u64,
+  pub fee: u64,
+}
+
+// Set as return data by burn and burn_cusd, fee is denominated in output token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BurnResult {
+  pub cusd_amount_in: u64,
+  pub amount_out: u64,
+  pub output_price: u64,
+  pub output_precision: u64,
+  pub fee: u64,
+}
+
+// Decode the result of the last mint invoked through CPI, None when the return data was not set by this program
+pub fn get_mint_result() -> Option<MintResult> {
+  let (program_id, data) = get_return_data()?;
+  if program_id != crate::ID {
+    return None;
+  }
+  MintResult::try_from_slice(&data).ok()
+}
+
+// Decode the result of the last burn invoked through CPI, None when the return data was not set by this program
+pub fn get_burn_result() -> Option<BurnResult> {
+  let (program_id, data) = get_return_data()?;
+  if program_id != crate::ID {
+    return None;
+  }
+  BurnResult::try_from_slice(&data).ok()
+}
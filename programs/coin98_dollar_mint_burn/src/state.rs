@@ -1,16 +1,178 @@
 use anchor_lang::prelude::*;
+use crate::constant::{
+  MAX_ADMIN_COUNT,
+  MAX_EXTRA_PRICE_FEED_COUNT,
+  MAX_INPUT_TOKEN_COUNT,
+  MAX_ROLE_MEMBER_COUNT,
+};
+use crate::error::{
+  ErrorCode,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+  ConfigManager,
+  Pauser,
+  Treasurer,
+  MintAuthorityCustodian,
+  Signer,
+}
+
+#[account]
+pub struct AdminRegistry {
+  pub nonce: u8,
+  pub admins: Vec<Pubkey>,
+  pub pending_admin: Pubkey,
+  pub pending_admin_proposer: Pubkey,
+  pub config_managers: Vec<Pubkey>,
+  pub pausers: Vec<Pubkey>,
+  pub treasurers: Vec<Pubkey>,
+  pub mint_authority_custodians: Vec<Pubkey>,
+  pub signers: Vec<Pubkey>,
+  pub threshold: u8,
+}
+
+impl AdminRegistry {
+  pub const LEN: usize = 1 + (4 + 32 * MAX_ADMIN_COUNT) + 32 + 32 + 5 * (4 + 32 * MAX_ROLE_MEMBER_COUNT) + 1;
+
+  pub fn is_admin(&self, user: &Pubkey) -> bool {
+    self.admins.contains(user)
+  }
+
+  pub fn has_role(&self, role: Role, user: &Pubkey) -> bool {
+    self.members(role).contains(user)
+  }
+
+  pub fn members(&self, role: Role) -> &Vec<Pubkey> {
+    match role {
+      Role::ConfigManager => &self.config_managers,
+      Role::Pauser => &self.pausers,
+      Role::Treasurer => &self.treasurers,
+      Role::MintAuthorityCustodian => &self.mint_authority_custodians,
+      Role::Signer => &self.signers,
+    }
+  }
+
+  pub fn members_mut(&mut self, role: Role) -> &mut Vec<Pubkey> {
+    match role {
+      Role::ConfigManager => &mut self.config_managers,
+      Role::Pauser => &mut self.pausers,
+      Role::Treasurer => &mut self.treasurers,
+      Role::MintAuthorityCustodian => &mut self.mint_authority_custodians,
+      Role::Signer => &mut self.signers,
+    }
+  }
+}
 
 #[account]
 pub struct AppData {
   pub nonce: u8,
   pub signer_nonce: u8,
   pub limit: u32,
+  pub timelock_delay: u32,
+  pub is_paused: bool,
+  pub is_mint_paused: bool,
+  pub is_burn_paused: bool,
+  pub max_price_age: u32,
+  pub treasury: Pubkey, // owner of the token accounts receiving collected fees
+  pub fee_signer_nonce: u8, // PDA owning the token accounts holding burn fees until collected
 }
 
 impl AppData {
+  pub const LEN: usize = 1 + 1 + 4 + 4 + 1 + 1 + 1 + 4 + 32 + 1;
+}
+
+// AppData as created before the timelock, read by migrate_app_data
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyAppData {
+  pub nonce: u8,
+  pub signer_nonce: u8,
+  pub limit: u32,
+}
+
+impl LegacyAppData {
   pub const LEN: usize = 1 + 1 + 4;
 }
 
+// Fixed means 1:1 with CUSD and must be paired with system_program::ID as price feed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OracleType {
+  Fixed,
+  Chainlink,
+  Pyth,
+}
+
+impl Default for OracleType {
+  fn default() -> Self {
+    OracleType::Chainlink
+  }
+}
+
+// Twap and LessFavourable need the writable TWAP account of the primary feed,
+// LessFavourable takes the lower of spot and TWAP on mint and the higher on burn
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PriceMode {
+  Spot,
+  Twap,
+  LessFavourable,
+}
+
+impl Default for PriceMode {
+  fn default() -> Self {
+    PriceMode::Spot
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceFeed {
+  pub oracle_type: OracleType,
+  pub address: Pubkey,
+}
+
+impl PriceFeed {
+  pub const LEN: usize = 1 + 32;
+}
+
+// Prices are expressed in CUSD with CUSD_PRECISION, zero means no bound
+// Extra feeds are aggregated with the primary feed by median, quorum counts fresh feeds including
+// the primary one and must be a majority of the feeds once extra feeds are configured
+// and max_divergence is the spread allowed between fresh feeds in basis points, zero disables it
+// max_deviation is the move allowed from the last accepted price within deviation_window seconds,
+// in basis points with zero disabling it, beyond it the price is rejected or the minter/burner paused
+// A Fixed collateral stays priced 1:1 but its extra feeds, when present, are read as reference
+// and the operation is rejected once they move more than max_peg_deviation basis points from 1 CUSD
+// max_confidence_ratio is the widest confidence accepted in basis points of the price, zero means no limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PriceConfig {
+  pub oracle_type: OracleType,
+  pub min_price: u64,
+  pub max_price: u64,
+  pub extra_price_feeds: Vec<PriceFeed>,
+  pub quorum: u8,
+  pub max_divergence: u16,
+  pub max_deviation: u16,
+  pub deviation_window: u32,
+  pub pause_on_deviation: bool,
+  pub max_peg_deviation: u16,
+  pub max_confidence_ratio: u16,
+  pub price_mode: PriceMode,
+}
+
+impl PriceConfig {
+  pub const LEN: usize = 1 + 8 + 8 + (4 + PriceFeed::LEN * MAX_EXTRA_PRICE_FEED_COUNT) + 1 + 2 + 2 + 4 + 1 + 2 + 2 + 1;
+}
+
+// Price in CUSD with CUSD_PRECISION last used by a mint or burn
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AcceptedPrice {
+  pub price: u64,
+  pub timestamp: i64,
+}
+
+impl AcceptedPrice {
+  pub const LEN: usize = 8 + 8;
+}
+
 #[account]
 pub struct Minter {
   pub nonce: u8,
@@ -19,6 +181,8 @@ pub struct Minter {
   pub input_decimals: Vec<u16>,
   pub input_percentages: Vec<u16>,
   pub input_price_feeds: Vec<Pubkey>,
+  pub input_price_configs: Vec<PriceConfig>,
+  pub input_last_prices: Vec<AcceptedPrice>,
   pub fee_percent: u16,
   pub accumulated_fee: u64,
   pub total_minted_amount: u64,
@@ -32,7 +196,7 @@ pub struct Minter {
 impl Minter {
   pub fn size(token_count: u8) -> usize {
     let token_count = usize::from(token_count);
-    1 + 1 + (4 + 32 * token_count) + (4 + 2 * token_count) + (4 + 2 * token_count) + (4 + 32 * token_count) + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8
+    1 + 1 + (4 + 32 * token_count) + (4 + 2 * token_count) + (4 + 2 * token_count) + (4 + 32 * token_count) + (4 + PriceConfig::LEN * token_count) + (4 + AcceptedPrice::LEN * token_count) + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8
   }
 }
 
@@ -43,6 +207,8 @@ pub struct Burner {
   pub output_token: Pubkey,
   pub output_decimals: u16,
   pub output_price_feed: Pubkey,
+  pub output_price_config: PriceConfig,
+  pub output_last_price: AcceptedPrice,
   pub fee_percent: u16,
   pub accumulated_fee: u64,
   pub total_burned_amount: u64,
@@ -54,5 +220,236 @@ pub struct Burner {
 }
 
 impl Burner {
-  pub const LEN: usize = 1 + 1 + 32 + 2 + 32 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+  pub const LEN: usize = 1 + 1 + 32 + 2 + 32 + PriceConfig::LEN + AcceptedPrice::LEN + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+}
+
+// Minter as created before price configs, read by migrate_minter
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyMinter {
+  pub nonce: u8,
+  pub is_active: bool,
+  pub input_tokens: Vec<Pubkey>,
+  pub input_decimals: Vec<u16>,
+  pub input_percentages: Vec<u16>,
+  pub input_price_feeds: Vec<Pubkey>,
+  pub fee_percent: u16,
+  pub accumulated_fee: u64,
+  pub total_minted_amount: u64,
+  pub total_minted_limit: u64,
+  pub per_period_minted_amount: u64,
+  pub per_period_minted_limit: u64,
+  pub last_period_timestamp: i64,
+  pub min_amount: u64,
+}
+
+// Burner as created before price configs, read by migrate_burner
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyBurner {
+  pub nonce: u8,
+  pub is_active: bool,
+  pub output_token: Pubkey,
+  pub output_decimals: u16,
+  pub output_price_feed: Pubkey,
+  pub fee_percent: u16,
+  pub accumulated_fee: u64,
+  pub total_burned_amount: u64,
+  pub total_burned_limit: u64,
+  pub per_period_burned_amount: u64,
+  pub per_period_burned_limit: u64,
+  pub last_period_timestamp: i64,
+  pub min_amount: u64,
+}
+
+// Arguments of set_minter, carried whole by Operation::SetMinter
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MinterConfig {
+  pub is_active: bool,
+  pub input_tokens: Vec<Pubkey>,
+  pub input_decimals: Vec<u16>,
+  pub input_percentages: Vec<u16>,
+  pub input_price_feeds: Vec<Pubkey>,
+  pub input_price_configs: Vec<PriceConfig>,
+  pub fee_percent: u16,
+  pub total_minted_limit: u64,
+  pub per_period_minted_limit: u64,
+  pub min_amount: u64,
+}
+
+impl MinterConfig {
+  pub const LEN: usize = 1 + (4 + 32 * MAX_INPUT_TOKEN_COUNT) + (4 + 2 * MAX_INPUT_TOKEN_COUNT) + (4 + 2 * MAX_INPUT_TOKEN_COUNT) + (4 + 32 * MAX_INPUT_TOKEN_COUNT) + (4 + PriceConfig::LEN * MAX_INPUT_TOKEN_COUNT) + 2 + 8 + 8 + 8;
+}
+
+// Arguments of set_burner, carried whole by Operation::SetBurner
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BurnerConfig {
+  pub is_active: bool,
+  pub output_token: Pubkey,
+  pub output_decimals: u16,
+  pub output_price_feed: Pubkey,
+  pub output_price_config: PriceConfig,
+  pub fee_percent: u16,
+  pub total_burned_limit: u64,
+  pub per_period_burned_limit: u64,
+  pub min_amount: u64,
+}
+
+impl BurnerConfig {
+  pub const LEN: usize = 1 + 32 + 2 + 32 + PriceConfig::LEN + 2 + 8 + 8 + 8;
+}
+
+// Prices are stored with TWAP_EXPONENT, the average is taken from the start of the previous window
+// so it always covers between one and two windows once the accumulator is warmed up
+// poke, mint and burn record prices, validated against price_config like a spot price of a minter,
+// its extra feeds must lead the extra feeds of the minter or burner token priced with the TWAP
+#[account]
+pub struct Twap {
+  pub nonce: u8,
+  pub price_feed: Pubkey,
+  pub window: u32,
+  pub price_config: PriceConfig,
+  pub last_price: u64,
+  pub last_timestamp: i64,
+  pub cumulative_price: u128,
+  pub previous_window_cumulative_price: u128,
+  pub previous_window_timestamp: i64,
+  pub current_window_cumulative_price: u128,
+  pub current_window_timestamp: i64,
+}
+
+impl Twap {
+  pub const LEN: usize = 1 + 32 + 4 + PriceConfig::LEN + 8 + 8 + 16 + 16 + 8 + 16 + 8;
+
+  pub fn update(&mut self, price: u64, timestamp: i64) -> Result<()> {
+    if self.last_timestamp == 0 {
+      self.current_window_timestamp = timestamp;
+    }
+    else if timestamp > self.last_timestamp {
+      let elapsed = u128::try_from(timestamp - self.last_timestamp).map_err(|_| ErrorCode::MathOverflow)?;
+      self.cumulative_price = u128::from(self.last_price).checked_mul(elapsed)
+        .and_then(|accumulated| self.cumulative_price.checked_add(accumulated))
+        .ok_or(ErrorCode::MathOverflow)?;
+    }
+    self.last_price = price;
+    self.last_timestamp = timestamp;
+
+    if timestamp - self.current_window_timestamp >= i64::from(self.window) {
+      self.previous_window_cumulative_price = self.current_window_cumulative_price;
+      self.previous_window_timestamp = self.current_window_timestamp;
+      self.current_window_cumulative_price = self.cumulative_price;
+      self.current_window_timestamp = timestamp;
+    }
+
+    Ok(())
+  }
+
+  // Falls back to the last price until some time has been accumulated
+  pub fn average(&self) -> Result<u64> {
+    let (start_cumulative_price, start_timestamp) = if self.previous_window_timestamp > 0 {
+      (self.previous_window_cumulative_price, self.previous_window_timestamp)
+    }
+    else {
+      (self.current_window_cumulative_price, self.current_window_timestamp)
+    };
+    if self.last_timestamp <= start_timestamp {
+      return Ok(self.last_price);
+    }
+
+    let elapsed = u128::try_from(self.last_timestamp - start_timestamp).map_err(|_| ErrorCode::MathOverflow)?;
+    let average = self.cumulative_price.checked_sub(start_cumulative_price).ok_or(ErrorCode::MathOverflow)? / elapsed;
+    let average = u64::try_from(average).map_err(|_| ErrorCode::MathOverflow)?;
+    Ok(average)
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+  WithdrawToken {
+    pool_token: Pubkey,
+    recipient_token: Pubkey,
+    amount: u64,
+  },
+  UnlockTokenMint {
+    token_mint: Pubkey,
+    new_authority: Pubkey,
+  },
+  SetMinterLimits {
+    minter: Pubkey,
+    total_minted_limit: u64,
+    per_period_minted_limit: u64,
+  },
+  SetBurnerLimits {
+    burner: Pubkey,
+    total_burned_limit: u64,
+    per_period_burned_limit: u64,
+  },
+  SetTimelockDelay {
+    timelock_delay: u32,
+  },
+  SetThreshold {
+    threshold: u8,
+  },
+  SetTreasury {
+    treasury: Pubkey,
+  },
+  SetMinter {
+    minter: Pubkey,
+    config: MinterConfig,
+  },
+  SetBurner {
+    burner: Pubkey,
+    config: BurnerConfig,
+  },
+  GrantSigner {
+    member: Pubkey,
+  },
+  SetLimit {
+    limit: u32,
+  },
+  SetMaxPriceAge {
+    max_price_age: u32,
+  },
+}
+
+impl Operation {
+  // size of the largest variant
+  pub const LEN: usize = 1 + 32 + MinterConfig::LEN;
+
+  pub fn required_role(&self) -> Role {
+    match self {
+      Operation::WithdrawToken { .. } => Role::Treasurer,
+      Operation::UnlockTokenMint { .. } => Role::MintAuthorityCustodian,
+      Operation::SetMinterLimits { .. } => Role::ConfigManager,
+      Operation::SetBurnerLimits { .. } => Role::ConfigManager,
+      Operation::SetTimelockDelay { .. } => Role::ConfigManager,
+      Operation::SetThreshold { .. } => Role::ConfigManager,
+      Operation::SetTreasury { .. } => Role::Treasurer,
+      Operation::SetMinter { .. } => Role::ConfigManager,
+      Operation::SetBurner { .. } => Role::ConfigManager,
+      Operation::GrantSigner { .. } => Role::ConfigManager,
+      Operation::SetLimit { .. } => Role::ConfigManager,
+      Operation::SetMaxPriceAge { .. } => Role::ConfigManager,
+    }
+  }
+}
+
+#[account]
+pub struct TimelockOperation {
+  pub nonce: u8,
+  pub proposer: Pubkey,
+  pub operation: Operation,
+  pub eta: i64,
+  pub approvals: Vec<Pubkey>,
+  pub is_executed: bool,
+  pub is_cancelled: bool,
+}
+
+impl TimelockOperation {
+  pub const LEN: usize = 1 + 32 + Operation::LEN + 8 + (4 + 32 * MAX_ROLE_MEMBER_COUNT) + 1 + 1;
+
+  // Only approvals from keys that still hold the signer role are counted
+  pub fn approval_count(&self, admin_registry: &AdminRegistry) -> usize {
+    self.approvals.iter()
+      .filter(|approver| admin_registry.has_role(Role::Signer, approver))
+      .count()
+  }
 }
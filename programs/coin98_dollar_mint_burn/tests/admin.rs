@@ -0,0 +1,189 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use solana_program_test::*;
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::AdminRegistry;
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+
+#[tokio::test]
+async fn add_and_remove_admin() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let second_admin = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let add_admin = add_admin_instruction(&payer_wallet.pubkey(), &second_admin.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, add_admin]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // the seat is only taken once the new admin accepts
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.admins, Vec::from([payer_wallet.pubkey()]));
+    assert_eq!(registry.pending_admin, second_admin.pubkey());
+
+    let accept_admin = accept_admin_instruction(&second_admin.pubkey());
+    process_transaction(&mut context, &Vec::from([accept_admin]), &Vec::from([&payer_wallet, &second_admin])).await.unwrap();
+
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.admins, Vec::from([payer_wallet.pubkey(), second_admin.pubkey()]));
+    assert_eq!(registry.pending_admin, Pubkey::default());
+
+    let remove_admin = remove_admin_instruction(&second_admin.pubkey(), &payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([remove_admin]), &Vec::from([&payer_wallet, &second_admin])).await.unwrap();
+
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.admins, Vec::from([second_admin.pubkey()]));
+}
+
+#[tokio::test]
+async fn add_admin_without_admin() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let outsider = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    process_transaction(&mut context, &Vec::from([create_admin_registry]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let add_admin = add_admin_instruction(&outsider.pubkey(), &outsider.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([add_admin]), &Vec::from([&payer_wallet, &outsider])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+}
+
+#[tokio::test]
+async fn remove_last_admin() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    process_transaction(&mut context, &Vec::from([create_admin_registry]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let remove_admin = remove_admin_instruction(&payer_wallet.pubkey(), &payer_wallet.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([remove_admin]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.admins, Vec::from([payer_wallet.pubkey()]));
+}
+
+#[tokio::test]
+async fn propose_and_accept_admin() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let new_admin = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let propose_admin = propose_admin_instruction(&payer_wallet.pubkey(), &new_admin.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, propose_admin]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // only the proposed key can accept
+    let outsider = Keypair::new();
+    let accept_admin = accept_admin_instruction(&outsider.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([accept_admin]), &Vec::from([&payer_wallet, &outsider])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    // the new admin takes over the seat of the proposer
+    let accept_admin = accept_admin_instruction(&new_admin.pubkey());
+    process_transaction(&mut context, &Vec::from([accept_admin]), &Vec::from([&payer_wallet, &new_admin])).await.unwrap();
+
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.admins, Vec::from([new_admin.pubkey()]));
+    assert_eq!(registry.pending_admin, Pubkey::default());
+    assert_eq!(registry.pending_admin_proposer, Pubkey::default());
+}
+
+#[tokio::test]
+async fn cancel_admin_transfer() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let new_admin = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let propose_admin = propose_admin_instruction(&payer_wallet.pubkey(), &new_admin.pubkey());
+    let cancel_admin_transfer = cancel_admin_transfer_instruction(&payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, propose_admin, cancel_admin_transfer]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let accept_admin = accept_admin_instruction(&new_admin.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([accept_admin]), &Vec::from([&payer_wallet, &new_admin])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.admins, Vec::from([payer_wallet.pubkey()]));
+    assert_eq!(registry.pending_admin, Pubkey::default());
+}
+
+#[tokio::test]
+async fn propose_admin_while_pending() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let new_admin = Keypair::new();
+    let other_admin = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let propose_admin = propose_admin_instruction(&payer_wallet.pubkey(), &new_admin.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, propose_admin]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // neither instruction may overwrite the pending proposal
+    let add_admin = add_admin_instruction(&payer_wallet.pubkey(), &other_admin.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([add_admin]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+    let propose_admin = propose_admin_instruction(&payer_wallet.pubkey(), &other_admin.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([propose_admin]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.pending_admin, new_admin.pubkey());
+    assert_eq!(registry.pending_admin_proposer, payer_wallet.pubkey());
+
+    // free again once cancelled
+    let cancel_admin_transfer = cancel_admin_transfer_instruction(&payer_wallet.pubkey());
+    let add_admin = add_admin_instruction(&payer_wallet.pubkey(), &other_admin.pubkey());
+    process_transaction(&mut context, &Vec::from([cancel_admin_transfer, add_admin]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.pending_admin, other_admin.pubkey());
+    assert_eq!(registry.pending_admin_proposer, Pubkey::default());
+}
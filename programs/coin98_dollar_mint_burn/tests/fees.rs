@@ -0,0 +1,88 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::Transaction,
+    transport::TransportError,
+};
+use solana_program_test::*;
+use coin98_dollar_mint_burn::state::{Burner, Minter, OracleType, PriceConfig, Role};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+use utils::pyth::*;
+use utils::fixture::*;
+
+#[tokio::test]
+async fn collect_mint_fees() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    // the creator of app data is the initial treasury
+    let grant_treasurer = grant_role_instruction(&payer_wallet.pubkey(), Role::Treasurer, &payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([grant_treasurer]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    assert_eq!(get_token_balance(&mut context, &setup.payer_cusd_token_account).await, 990_000_000);
+
+    let collect_fees = collect_fees_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, Vec::from([setup.minter, setup.payer_cusd_token_account]));
+    process_transaction(&mut context, &Vec::from([collect_fees]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.payer_cusd_token_account).await, 1_000_000_000);
+    let minter_account: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert_eq!(minter_account.accumulated_fee, 0);
+}
+
+#[tokio::test]
+async fn collect_burn_fees() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let grant_treasurer = grant_role_instruction(&payer_wallet.pubkey(), Role::Treasurer, &payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([grant_treasurer]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let minter = &setup.minter;
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, minter.payer_c98_token_account, minter.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    let payer_c98_balance = get_token_balance(&mut context, &minter.payer_c98_token_account).await;
+    let pool_c98_balance = get_token_balance(&mut context, &minter.pool_c98_token_account).await;
+
+    // 100 c98 for 250 CUSD, 1 c98 of fee leaves the pool for the fee account
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account, setup.fee_c98_token_account]), 100, 250_000_000);
+    process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &minter.payer_c98_token_account).await, payer_c98_balance + 99);
+    assert_eq!(get_token_balance(&mut context, &minter.pool_c98_token_account).await, pool_c98_balance - 100);
+    assert_eq!(get_token_balance(&mut context, &setup.fee_c98_token_account).await, 1);
+
+    let collect_fees = collect_fees_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, Vec::from([setup.burner, setup.fee_c98_token_account, minter.payer_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([collect_fees]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &minter.payer_c98_token_account).await, payer_c98_balance + 100);
+    assert_eq!(get_token_balance(&mut context, &setup.fee_c98_token_account).await, 0);
+    let burner_account: Burner = get_anchor_account(&mut context, &setup.burner).await;
+    assert_eq!(burner_account.accumulated_fee, 0);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "unit-test")]
+
+use coin98_dollar_mint_burn::math::*;
+
+const ITERATIONS: usize = 10_000;
+
+// Random u64 with a random bit length so small and overflowing values are both covered
+fn random_u64() -> u64 {
+    let shift = rand::random::<u32>() % 64;
+    rand::random::<u64>() >> shift
+}
+
+fn reference_mul_div(number: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    let product = u128::from(number) * u128::from(numerator);
+    let denominator = u128::from(denominator);
+    let result = match rounding {
+        Rounding::Floor => product / denominator,
+        Rounding::Ceil => (product + denominator - 1) / denominator,
+    };
+    u64::try_from(result).ok()
+}
+
+#[test]
+fn checked_add_matches_reference() {
+    for _ in 0..ITERATIONS {
+        let (a, b) = (random_u64(), random_u64());
+        let expected = u64::try_from(u128::from(a) + u128::from(b)).ok();
+        assert_eq!(checked_add(a, b).ok(), expected, "{} + {}", a, b);
+    }
+}
+
+#[test]
+fn checked_sub_matches_reference() {
+    for _ in 0..ITERATIONS {
+        let (a, b) = (random_u64(), random_u64());
+        let expected = u64::try_from(i128::from(a) - i128::from(b)).ok();
+        assert_eq!(checked_sub(a, b).ok(), expected, "{} - {}", a, b);
+    }
+}
+
+#[test]
+fn checked_mul_div_matches_reference() {
+    for _ in 0..ITERATIONS {
+        let (number, numerator, denominator) = (random_u64(), random_u64(), random_u64());
+        for rounding in [Rounding::Floor, Rounding::Ceil] {
+            assert_eq!(
+                checked_mul_div(number, numerator, denominator, rounding).ok(),
+                reference_mul_div(number, numerator, denominator, rounding),
+                "{} * {} / {} {:?}", number, numerator, denominator, rounding,
+            );
+        }
+    }
+}
+
+#[test]
+fn checked_mul_div_rounds_towards_the_protocol() {
+    for _ in 0..ITERATIONS {
+        let (number, numerator, denominator) = (random_u64(), random_u64(), random_u64().max(1));
+        let floor = checked_mul_div(number, numerator, denominator, Rounding::Floor);
+        let ceil = checked_mul_div(number, numerator, denominator, Rounding::Ceil);
+        if let (Ok(floor), Ok(ceil)) = (floor, ceil) {
+            let is_exact = (u128::from(number) * u128::from(numerator)) % u128::from(denominator) == 0;
+            assert_eq!(ceil - floor, if is_exact { 0 } else { 1 });
+        }
+    }
+
+    assert_eq!(checked_mul_div(10, 1, 3, Rounding::Floor).ok(), Some(3));
+    assert_eq!(checked_mul_div(10, 1, 3, Rounding::Ceil).ok(), Some(4));
+    assert_eq!(checked_mul_div(u64::MAX, u64::MAX, u64::MAX, Rounding::Ceil).ok(), Some(u64::MAX));
+    assert!(checked_mul_div(u64::MAX, 2, 1, Rounding::Floor).is_err());
+    assert!(checked_mul_div(1, 1, 0, Rounding::Floor).is_err());
+}
+
+#[test]
+fn checked_pow10_matches_reference() {
+    for exponent in 0..=40u32 {
+        let expected = u64::try_from(10u128.checked_pow(exponent).unwrap_or(u128::MAX)).ok();
+        assert_eq!(checked_pow10(exponent).ok(), expected, "10^{}", exponent);
+    }
+}
+
+#[test]
+fn period_end_matches_reference() {
+    for _ in 0..ITERATIONS {
+        let start_timestamp = rand::random::<i64>() >> (rand::random::<u32>() % 64);
+        let hours = rand::random::<u32>();
+        let expected = i64::try_from(i128::from(start_timestamp) + i128::from(hours) * 3600).ok();
+        assert_eq!(period_end(start_timestamp, hours).ok(), expected, "{} + {}h", start_timestamp, hours);
+    }
+
+    assert_eq!(period_end(0, u32::MAX).ok(), Some(i64::from(u32::MAX) * 3600));
+    assert!(period_end(i64::MAX, 1).is_err());
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    account::{Account, AccountSharedData},
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use solana_program_test::*;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use coin98_dollar_mint_burn::constant::MAX_INPUT_TOKEN_COUNT;
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::{AcceptedPrice, Burner, LegacyBurner, LegacyMinter, Minter, OracleType};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+
+// Writes a program owned account holding data after the discriminator, padded to len
+async fn set_legacy_account(context: &mut ProgramTestContext, address: &Pubkey, discriminator: [u8; 8], data: Vec<u8>, len: usize) {
+    let mut account_data = discriminator.to_vec();
+    account_data.extend(data);
+    account_data.resize(len, 0);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = Account {
+        lamports: rent.minimum_balance(account_data.len()),
+        data: account_data,
+        owner: coin98_dollar_mint_burn::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(address, &AccountSharedData::from(account));
+}
+
+#[tokio::test]
+async fn migrate_legacy_minter() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let other_wallet = Keypair::new();
+    airdrop(&mut context, &other_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let c98_mint = Pubkey::new_unique();
+    let usdc_mint = Pubkey::new_unique();
+    let c98_feed = Pubkey::new_unique();
+    let legacy_minter = LegacyMinter {
+        nonce: 255,
+        is_active: true,
+        input_tokens: Vec::from([c98_mint, usdc_mint]),
+        input_decimals: Vec::from([6, 6]),
+        input_percentages: Vec::from([5000, 5000]),
+        input_price_feeds: Vec::from([c98_feed, solana_sdk::system_program::id()]),
+        fee_percent: 10,
+        accumulated_fee: 0,
+        total_minted_amount: 1_000,
+        total_minted_limit: 1_000_000,
+        per_period_minted_amount: 100,
+        per_period_minted_limit: 10_000,
+        last_period_timestamp: 1,
+        min_amount: 10,
+    };
+    let minter = Pubkey::new_unique();
+    // legacy minters were created with room for eight input tokens
+    let legacy_len = 16 + 1 + 1 + (4 + 32 * 8) + (4 + 2 * 8) + (4 + 2 * 8) + (4 + 32 * 8) + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+    set_legacy_account(&mut context, &minter, Minter::discriminator(), legacy_minter.try_to_vec().unwrap(), legacy_len).await;
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    process_transaction(&mut context, &Vec::from([create_admin_registry]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // reserved to registry admins
+    let migrate_minter = migrate_minter_instruction(&other_wallet.pubkey(), &minter);
+    let result = try_process_transaction(&mut context, &Vec::from([migrate_minter]), &Vec::from([&other_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let migrate_minter = migrate_minter_instruction(&payer_wallet.pubkey(), &minter);
+    process_transaction(&mut context, &Vec::from([migrate_minter]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let account = context.banks_client.get_account(minter).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), 16 + Minter::size(MAX_INPUT_TOKEN_COUNT as u8));
+    let minter_account: Minter = get_anchor_account(&mut context, &minter).await;
+    assert_eq!(minter_account.input_tokens, Vec::from([c98_mint, usdc_mint]));
+    assert_eq!(minter_account.input_price_configs[0].oracle_type, OracleType::Chainlink);
+    assert_eq!(minter_account.input_price_configs[1].oracle_type, OracleType::Fixed);
+    assert_eq!(minter_account.input_last_prices, Vec::from([AcceptedPrice::default(); 2]));
+    assert_eq!(minter_account.total_minted_amount, 1_000);
+    assert_eq!(minter_account.per_period_minted_limit, 10_000);
+    assert_eq!(minter_account.min_amount, 10);
+
+    // a migrated minter is left alone, the second instruction only keeps the transaction distinct from the first one
+    let migrate_minter = migrate_minter_instruction(&payer_wallet.pubkey(), &minter);
+    let migrate_burner = migrate_burner_instruction(&payer_wallet.pubkey(), &minter);
+    let result = try_process_transaction(&mut context, &Vec::from([migrate_minter, migrate_burner]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+}
+
+#[tokio::test]
+async fn migrate_legacy_burner() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let c98_mint = Pubkey::new_unique();
+    let c98_feed = Pubkey::new_unique();
+    let legacy_burner = LegacyBurner {
+        nonce: 255,
+        is_active: true,
+        output_token: c98_mint,
+        output_decimals: 6,
+        output_price_feed: c98_feed,
+        fee_percent: 10,
+        accumulated_fee: 0,
+        total_burned_amount: 1_000,
+        total_burned_limit: 1_000_000,
+        per_period_burned_amount: 100,
+        per_period_burned_limit: 10_000,
+        last_period_timestamp: 1,
+        min_amount: 10,
+    };
+    let burner = Pubkey::new_unique();
+    let legacy_len = 16 + 1 + 1 + 32 + 2 + 32 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+    set_legacy_account(&mut context, &burner, Burner::discriminator(), legacy_burner.try_to_vec().unwrap(), legacy_len).await;
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    process_transaction(&mut context, &Vec::from([create_admin_registry]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // a burner is not read as a minter
+    let migrate_minter = migrate_minter_instruction(&payer_wallet.pubkey(), &burner);
+    let result = try_process_transaction(&mut context, &Vec::from([migrate_minter]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidAccount))),
+    );
+
+    let migrate_burner = migrate_burner_instruction(&payer_wallet.pubkey(), &burner);
+    process_transaction(&mut context, &Vec::from([migrate_burner]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let burner_account: Burner = get_anchor_account(&mut context, &burner).await;
+    assert_eq!(burner_account.output_token, c98_mint);
+    assert_eq!(burner_account.output_price_feed, c98_feed);
+    assert_eq!(burner_account.output_price_config.oracle_type, OracleType::Chainlink);
+    assert_eq!(burner_account.output_last_price, AcceptedPrice::default());
+    assert_eq!(burner_account.total_burned_amount, 1_000);
+    assert_eq!(burner_account.per_period_burned_limit, 10_000);
+}
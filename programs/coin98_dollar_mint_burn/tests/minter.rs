@@ -9,11 +9,15 @@ pub use solana_sdk::{
     transport::TransportError,
 };
 use solana_program_test::*;
-use std::time::*;
+use coin98_dollar_mint_burn::constant::DEFAULT_TIMELOCK_DELAY;
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::{Burner, Minter, OracleType, Operation, PriceConfig, Role};
 use utils::helper::*;
 use utils::wallet::*;
 use utils::instructions::*;
 use utils::chainlink::*;
+use utils::pyth::*;
+use utils::fixture::*;
 
 #[tokio::test]
 async fn create_minter() {
@@ -36,25 +40,34 @@ async fn create_minter() {
     let pool_cusd_token_account = create_associated_token_account(&mut context, &root_signer, &cusd_mint.pubkey()).await.unwrap();
     let payer_cusd_token_account = create_associated_token_account(&mut context, &payer_wallet.pubkey(), &cusd_mint.pubkey()).await.unwrap();
 
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
     let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
-    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 1_000_000_000);
-    process_transaction(&mut context, &Vec::from([create_app_data, set_app_data]), &Vec::from([&payer_wallet])).await.unwrap();
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 1_000_000_000, DEFAULT_TIMELOCK_DELAY, 3600);
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager, set_app_data]), &Vec::from([&payer_wallet])).await.unwrap();
 
     mint_tokens(&mut context, &c98_mint.pubkey(), &payer_c98_token_account, 1_000_000_000_000, &payer_wallet.pubkey(), Some(&payer_wallet)).await.unwrap();
 
     let (c98_feed, _): (Pubkey, u8) = find_feed_address(&feed_path);
-    let create_feed = create_feed_instruction(&payer_wallet.pubkey(), feed_path, 25, 75, "C98-USD".to_string(), 6, 10);
-    let submit_feed = submit_feed_instruction(&payer_wallet.pubkey(), &c98_feed, Instant::now().elapsed().as_secs().try_into().unwrap(), 1000000);
-    process_transaction(&mut context, &Vec::from([create_feed, submit_feed]), &Vec::from([&payer_wallet])).await.unwrap();
 
     let minter_path = (0..10).map(|_| { rand::random::<u8>() }).collect();
     let (minter, _) = find_minter_address(&minter_path);
     let create_minter = create_minter_instruction(&payer_wallet.pubkey(), minter_path);
-    let set_minter = set_minter_instruction(&payer_wallet.pubkey(), &minter, true, Vec::from([c98_mint.pubkey()]), Vec::from([0]), Vec::from([10000]), Vec::from([c98_feed.clone()]), 0, 1_000_000_000_000u64, 1_000_000_000_000u64, 0);
+    let set_minter = set_minter_instruction(&payer_wallet.pubkey(), &minter, true, Vec::from([c98_mint.pubkey()]), Vec::from([0]), Vec::from([10000]), Vec::from([c98_feed.clone()]), Vec::from([PriceConfig::default()]), 0, 0, 0, 0);
     process_transaction(&mut context, &Vec::from([create_minter, set_minter]), &Vec::from([&payer_wallet])).await.unwrap();
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::SetMinterLimits {
+        minter,
+        total_minted_limit: 1_000_000_000_000u64,
+        per_period_minted_limit: 1_000_000_000_000u64,
+    }, Vec::from([minter])).await;
+
+    let create_feed = create_feed_instruction(&payer_wallet.pubkey(), feed_path, 25, 75, "C98-USD".to_string(), 6, 10);
+    let clock = get_clock(&mut context).await;
+    let submit_feed = submit_feed_instruction(&payer_wallet.pubkey(), &c98_feed, clock.unix_timestamp, 1000000);
+    process_transaction(&mut context, &Vec::from([create_feed, submit_feed]), &Vec::from([&payer_wallet])).await.unwrap();
 
     let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
-    let mint = mint_instruction(&payer_wallet.pubkey(), &cusd_mint.pubkey(), &minter, &payer_cusd_token_account, 1_000_000_000_000, extra_instructions, Vec::from([c98_feed, payer_c98_token_account, pool_c98_token_account]));
+    let mint = mint_instruction(&payer_wallet.pubkey(), &cusd_mint.pubkey(), &minter, &payer_cusd_token_account, 1_000_000_000_000, extra_instructions, Vec::from([1_000_000]), Vec::from([c98_feed, payer_c98_token_account, pool_c98_token_account]));
     process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
 
     // burn
@@ -63,9 +76,158 @@ async fn create_minter() {
     let burner_path = (0..10).map(|_| { rand::random::<u8>() }).collect();
     let (burner, _) = find_burner_address(&burner_path);
     let create_burner  = create_burner_instruction(&payer_wallet.pubkey(), burner_path);
-    let set_burner = set_burner_instruction(&payer_wallet.pubkey(), &burner, true, c98_mint.pubkey(), 0, c98_feed.clone(), 0, 1_000_000_000_000u64, 1_000_000_000_000u64, 0);
+    let set_burner = set_burner_instruction(&payer_wallet.pubkey(), &burner, true, c98_mint.pubkey(), 0, c98_feed.clone(), PriceConfig::default(), 0, 0, 0, 0);
     process_transaction(&mut context, &Vec::from([create_burner, set_burner]), &Vec::from([&payer_wallet])).await.unwrap();
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::SetBurnerLimits {
+        burner,
+        total_burned_limit: 1_000_000_000_000u64,
+        per_period_burned_limit: 1_000_000_000_000u64,
+    }, Vec::from([burner])).await;
+
+    // the timelock delay made the last round stale
+    let clock = get_clock(&mut context).await;
+    let submit_feed = submit_feed_instruction(&payer_wallet.pubkey(), &c98_feed, clock.unix_timestamp, 1000000);
+    process_transaction(&mut context, &Vec::from([submit_feed]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let burn = burn_instruction(&payer_wallet.pubkey(), &cusd_mint.pubkey(), &burner, &pool_cusd_token_account, &payer_cusd_token_account, Vec::from([c98_feed, pool_c98_token_account, payer_c98_token_account]), 1_000, 1_000_000_000);
+    process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let burn_cusd = burn_cusd_instruction(&payer_wallet.pubkey(), &cusd_mint.pubkey(), &burner, &pool_cusd_token_account, &payer_cusd_token_account, Vec::from([c98_feed, pool_c98_token_account, payer_c98_token_account]), 1_000_000_000, 1_000);
+    process_transaction(&mut context, &Vec::from([burn_cusd]), &Vec::from([&payer_wallet])).await.unwrap();
+}
+
+#[tokio::test]
+async fn mint_with_max_input_amount_exceeded() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    // 1000 CUSD at 2.5 USD takes 400 c98
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([399]), remaining_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::MaxInputAmountExceeded))),
+    );
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 0);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([400]), remaining_accounts);
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+}
+
+#[tokio::test]
+async fn burn_with_max_cusd_in_exceeded() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
 
-    let burn = burn_instruction(&payer_wallet.pubkey(), &cusd_mint.pubkey(), &burner, &pool_cusd_token_account, &payer_cusd_token_account, Vec::from([c98_feed, pool_c98_token_account, payer_c98_token_account]), 1_000);
+    let minter = &setup.minter;
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, minter.payer_c98_token_account, minter.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account]);
+
+    // 100 c98 at 2.5 USD takes 250 CUSD
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts.clone(), 100, 249_999_999);
+    let result = try_process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::MaxCusdInExceeded))),
+    );
+    assert_eq!(get_token_balance(&mut context, &minter.payer_cusd_token_account).await, 1_000_000_000);
+
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 100, 250_000_000);
     process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await.unwrap();
+    assert_eq!(get_token_balance(&mut context, &minter.payer_cusd_token_account).await, 750_000_000);
+}
+
+#[tokio::test]
+async fn mint_exact_input() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 233_333_333, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    // 333 c98 at 2.33333333 USD is worth 776.999998 CUSD once floored, the 1% fee is rounded up
+    let mint = mint_exact_input_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 333, Vec::from([0, 1, 2]), 769_229_999, remaining_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::MinAmountOutNotMet))),
+    );
+
+    let mint = mint_exact_input_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 333, Vec::from([0, 1, 2]), 769_229_998, remaining_accounts);
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 333);
+    assert_eq!(get_token_balance(&mut context, &setup.payer_cusd_token_account).await, 769_229_998);
+    let minter: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert_eq!(minter.total_minted_amount, 776_999_998);
+    assert_eq!(minter.per_period_minted_amount, 776_999_998);
+    assert_eq!(minter.accumulated_fee, 7_770_000);
+}
+
+#[tokio::test]
+async fn burn_cusd() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 233_333_333, 0, -8, clock.unix_timestamp);
+
+    let minter = &setup.minter;
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, minter.payer_c98_token_account, minter.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    let payer_c98_balance = get_token_balance(&mut context, &minter.payer_c98_token_account).await;
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account, setup.fee_c98_token_account]);
+
+    // 776.999999 CUSD buys 332 c98 at 2.33333333 USD once floored, the 1% fee is rounded up to 4 c98
+    let burn_cusd = burn_cusd_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts.clone(), 776_999_999, 329);
+    let result = try_process_transaction(&mut context, &Vec::from([burn_cusd]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::MinAmountOutNotMet))),
+    );
+
+    let burn_cusd = burn_cusd_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 776_999_999, 328);
+    process_transaction(&mut context, &Vec::from([burn_cusd]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &minter.payer_cusd_token_account).await, 223_000_001);
+    assert_eq!(get_token_balance(&mut context, &minter.payer_c98_token_account).await, payer_c98_balance + 328);
+    assert_eq!(get_token_balance(&mut context, &setup.fee_c98_token_account).await, 4);
+    let burner: Burner = get_anchor_account(&mut context, &setup.burner).await;
+    assert_eq!(burner.total_burned_amount, 776_999_999);
+    assert_eq!(burner.per_period_burned_amount, 776_999_999);
+    assert_eq!(burner.accumulated_fee, 4);
 }
@@ -0,0 +1,863 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use solana_program_test::*;
+use coin98_dollar_mint_burn::constant::DEFAULT_TIMELOCK_DELAY;
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::{Minter, OracleType, Operation, PriceConfig, PriceMode, Role, Twap};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+use utils::chainlink::*;
+use utils::pyth::*;
+use utils::fixture::*;
+
+#[tokio::test]
+async fn mint_with_stale_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+
+    let c98_mint = Keypair::new();
+    create_mint(&mut context, &c98_mint, &payer_wallet.pubkey(), None).await.unwrap();
+    let cusd_mint = Keypair::from_bytes(&[202,192,162,73,184,144,236,61,88,204,128,42,118,116,110,72,153,114,57,183,67,59,239,160,46,130,112,92,219,145,116,21,171,46,92,155,111,121,107,137,187,201,219,116,208,23,156,137,19,146,184,45,122,164,241,252,184,1,174,7,13,160,189,174]).unwrap();
+    create_mint(&mut context, &cusd_mint, &root_signer, Some(&root_signer)).await.unwrap();
+
+    let payer_c98_token_account = create_associated_token_account(&mut context, &payer_wallet.pubkey(), &c98_mint.pubkey()).await.unwrap();
+    let pool_c98_token_account = create_associated_token_account(&mut context, &root_signer, &c98_mint.pubkey()).await.unwrap();
+    let payer_cusd_token_account = create_associated_token_account(&mut context, &payer_wallet.pubkey(), &cusd_mint.pubkey()).await.unwrap();
+    mint_tokens(&mut context, &c98_mint.pubkey(), &payer_c98_token_account, 1_000_000_000_000, &payer_wallet.pubkey(), Some(&payer_wallet)).await.unwrap();
+
+    let max_price_age: u32 = 60;
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 24, DEFAULT_TIMELOCK_DELAY, max_price_age);
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager, set_app_data]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let feed_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (c98_feed, _): (Pubkey, u8) = find_feed_address(&feed_path);
+
+    let minter_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (minter, _) = find_minter_address(&minter_path);
+    let create_minter = create_minter_instruction(&payer_wallet.pubkey(), minter_path);
+    let set_minter = set_minter_instruction(&payer_wallet.pubkey(), &minter, true, Vec::from([c98_mint.pubkey()]), Vec::from([0]), Vec::from([10000]), Vec::from([c98_feed.clone()]), Vec::from([PriceConfig::default()]), 0, 0, 0, 0);
+    process_transaction(&mut context, &Vec::from([create_minter, set_minter]), &Vec::from([&payer_wallet])).await.unwrap();
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::SetMinterLimits {
+        minter,
+        total_minted_limit: 1_000_000_000_000u64,
+        per_period_minted_limit: 1_000_000_000_000u64,
+    }, Vec::from([minter])).await;
+
+    let clock = get_clock(&mut context).await;
+    let create_feed = create_feed_instruction(&payer_wallet.pubkey(), feed_path, 25, 75, "C98-USD".to_string(), 6, 10);
+    let submit_feed = submit_feed_instruction(&payer_wallet.pubkey(), &c98_feed, clock.unix_timestamp, 1000000);
+    process_transaction(&mut context, &Vec::from([create_feed, submit_feed]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    set_unix_timestamp(&mut context, clock.unix_timestamp + i64::from(max_price_age) + 1).await;
+
+    let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &cusd_mint.pubkey(), &minter, &payer_cusd_token_account, 1_000_000, extra_instructions, Vec::from([1]), Vec::from([c98_feed, payer_c98_token_account, pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::StalePrice))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_pyth_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    // 2.5 USD per C98 with 8 decimals
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 100_000, -8, clock.unix_timestamp);
+
+    let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, extra_instructions, Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+}
+
+#[tokio::test]
+async fn mint_with_invalid_pyth_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 0, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidPrice))),
+    );
+
+    set_pyth_price_account(&mut context, &c98_feed, -250_000_000, 0, -8, clock.unix_timestamp);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 500_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidPrice))),
+    );
+}
+
+#[tokio::test]
+async fn burn_with_invalid_pyth_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, -250_000_000, 0, -8, clock.unix_timestamp);
+
+    let minter = &setup.minter;
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account]);
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 100, 1_000_000_000);
+    let result = try_process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidPrice))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_invalid_chainlink_answer() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feed_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (c98_feed, _): (Pubkey, u8) = find_feed_address(&feed_path);
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, PriceConfig::default(), 0).await;
+
+    let clock = get_clock(&mut context).await;
+    let create_feed = create_feed_instruction(&payer_wallet.pubkey(), feed_path, 25, 75, "C98-USD".to_string(), 6, 10);
+    let submit_feed = submit_feed_instruction(&payer_wallet.pubkey(), &c98_feed, clock.unix_timestamp, 0);
+    process_transaction(&mut context, &Vec::from([create_feed, submit_feed]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidPrice))),
+    );
+}
+
+#[tokio::test]
+async fn burn_with_invalid_chainlink_answer() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feed_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (c98_feed, _): (Pubkey, u8) = find_feed_address(&feed_path);
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, PriceConfig::default(), 0).await;
+
+    let clock = get_clock(&mut context).await;
+    let create_feed = create_feed_instruction(&payer_wallet.pubkey(), feed_path, 25, 75, "C98-USD".to_string(), 6, 10);
+    let submit_feed = submit_feed_instruction(&payer_wallet.pubkey(), &c98_feed, clock.unix_timestamp, -2_500_000);
+    process_transaction(&mut context, &Vec::from([create_feed, submit_feed]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let minter = &setup.minter;
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account]);
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 100, 1_000_000_000);
+    let result = try_process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidPrice))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_price_out_of_bounds() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        min_price: 3_000_000,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    // 2.5 USD is below the 3 USD floor
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceOutOfBounds))),
+    );
+}
+
+#[tokio::test]
+async fn burn_with_price_out_of_bounds() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_price: 2_000_000,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    // 2.5 USD is above the 2 USD cap
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let minter = &setup.minter;
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account]);
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 100, 1_000_000_000);
+    let result = try_process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceOutOfBounds))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_median_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feeds = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        extra_price_feeds: Vec::from([pyth_feed(&feeds[1]), pyth_feed(&feeds[2])]),
+        quorum: 3,
+        max_divergence: 1000,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &feeds[0], price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &feeds[0], 260_000_000, 0, -8, clock.unix_timestamp);
+    // an older feed with a coarser exponent, median is 2.5 USD
+    set_pyth_price_account(&mut context, &feeds[1], 25, 0, -1, clock.unix_timestamp - 10);
+    set_pyth_price_account(&mut context, &feeds[2], 240_000_000, 0, -8, clock.unix_timestamp);
+
+    let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, extra_instructions, Vec::from([1_000]), Vec::from([feeds[0], setup.payer_c98_token_account, setup.pool_c98_token_account, feeds[1], feeds[2]]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+}
+
+#[tokio::test]
+async fn mint_with_diverging_prices() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feeds = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        extra_price_feeds: Vec::from([pyth_feed(&feeds[1])]),
+        quorum: 2,
+        max_divergence: 1000,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &feeds[0], price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &feeds[0], 250_000_000, 0, -8, clock.unix_timestamp);
+    set_pyth_price_account(&mut context, &feeds[1], 200_000_000, 0, -8, clock.unix_timestamp);
+
+    let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, extra_instructions, Vec::from([1_000]), Vec::from([feeds[0], setup.payer_c98_token_account, setup.pool_c98_token_account, feeds[1]]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceDivergence))),
+    );
+}
+
+#[tokio::test]
+async fn mint_without_price_quorum() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feeds = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        extra_price_feeds: Vec::from([pyth_feed(&feeds[1])]),
+        quorum: 2,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &feeds[0], price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &feeds[0], 250_000_000, 0, -8, clock.unix_timestamp);
+    set_pyth_price_account(&mut context, &feeds[1], 250_000_000, 0, -8, clock.unix_timestamp - 61);
+
+    let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, extra_instructions, Vec::from([1_000]), Vec::from([feeds[0], setup.payer_c98_token_account, setup.pool_c98_token_account, feeds[1]]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::QuorumNotMet))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_stale_primary_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feeds = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        extra_price_feeds: Vec::from([pyth_feed(&feeds[1]), pyth_feed(&feeds[2])]),
+        quorum: 2,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &feeds[0], price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    // the stale primary feed is left out, the two fresh extra feeds meet the quorum
+    set_pyth_price_account(&mut context, &feeds[0], 100_000_000, 0, -8, clock.unix_timestamp - 61);
+    set_pyth_price_account(&mut context, &feeds[1], 250_000_000, 0, -8, clock.unix_timestamp);
+    set_pyth_price_account(&mut context, &feeds[2], 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let extra_instructions: Vec<u8> = Vec::from([0, 1, 2]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, extra_instructions, Vec::from([1_000]), Vec::from([feeds[0], setup.payer_c98_token_account, setup.pool_c98_token_account, feeds[1], feeds[2]]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+}
+
+#[tokio::test]
+async fn set_minter_with_minority_quorum() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let feeds = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &feeds[0], price_config, 0).await;
+
+    // one fresh extra feed out of three would be enough to price the collateral
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        extra_price_feeds: Vec::from([pyth_feed(&feeds[1]), pyth_feed(&feeds[2])]),
+        quorum: 1,
+        ..PriceConfig::default()
+    };
+    let set_minter = set_minter_instruction(&payer_wallet.pubkey(), &setup.minter, true, Vec::from([setup.c98_mint]), Vec::from([0]), Vec::from([10000]), Vec::from([feeds[0]]), Vec::from([price_config]), 0, 0, 0, 0);
+    let result = try_process_transaction(&mut context, &Vec::from([set_minter]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_price_deviation() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_deviation: 500,
+        deviation_window: 3600,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // 20% spike right after the accepted price
+    set_pyth_price_account(&mut context, &c98_feed, 300_000_000, 0, -8, clock.unix_timestamp);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 600_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceDeviation))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_price_deviation_pauses_minter() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_deviation: 500,
+        deviation_window: 3600,
+        pause_on_deviation: true,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    set_pyth_price_account(&mut context, &c98_feed, 200_000_000, 0, -8, clock.unix_timestamp);
+
+    // the mint fails without moving funds, the pause is persisted by anyone through the breaker
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 500_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::CircuitBreakerTripped))),
+    );
+
+    let trip = trip_minter_circuit_breaker_instruction(&setup.minter, remaining_accounts);
+    process_transaction(&mut context, &Vec::from([trip]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let minter: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert!(!minter.is_active);
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+}
+
+#[tokio::test]
+async fn mint_with_price_walked_within_window() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_deviation: 500,
+        deviation_window: 3600,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // each step stays within 5% of the previous price but the reference is kept for the whole window
+    set_pyth_price_account(&mut context, &c98_feed, 260_000_000, 0, -8, clock.unix_timestamp);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 520_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    set_pyth_price_account(&mut context, &c98_feed, 270_000_000, 0, -8, clock.unix_timestamp);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 540_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceDeviation))),
+    );
+}
+
+#[tokio::test]
+async fn mint_pegged_collateral_with_reference_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let reference_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Fixed,
+        extra_price_feeds: Vec::from([pyth_feed(&reference_feed)]),
+        max_peg_deviation: 200,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &solana_sdk::system_program::id(), price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &reference_feed, 99_500_000, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([solana_sdk::system_program::id(), setup.payer_c98_token_account, setup.pool_c98_token_account, reference_feed]);
+
+    // minted 1:1 while the reference stays within the band
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 1_000);
+
+    set_pyth_price_account(&mut context, &reference_feed, 95_000_000, 0, -8, clock.unix_timestamp);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 500_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Depegged))),
+    );
+}
+
+#[tokio::test]
+async fn mint_at_price_minus_confidence() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_confidence_ratio: 2500,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    // 2.5 ± 0.5 USD, collateral is valued at 2 USD
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 50_000_000, -8, clock.unix_timestamp);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 500);
+}
+
+#[tokio::test]
+async fn mint_with_wide_confidence() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_confidence_ratio: 1000,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 50_000_000, -8, clock.unix_timestamp);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::ConfidenceTooWide))),
+    );
+}
+
+#[tokio::test]
+async fn mint_at_twap() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        price_mode: PriceMode::Twap,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let twap_price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let create_twap = create_twap_instruction(&payer_wallet.pubkey(), &c98_feed, 600, twap_price_config);
+    let poke = poke_instruction(&c98_feed, Vec::new());
+    process_transaction(&mut context, &Vec::from([create_twap, poke]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // spot doubles 100 seconds later, the TWAP has not been poked since
+    set_unix_timestamp(&mut context, clock.unix_timestamp + 100).await;
+    set_pyth_price_account(&mut context, &c98_feed, 500_000_000, 0, -8, clock.unix_timestamp + 100);
+
+    // mint records the new spot price, the average over that time is still 2.5 USD
+    let (twap, _): (Pubkey, u8) = find_twap_address(&c98_feed);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account, twap]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts);
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+    let twap_account: Twap = get_anchor_account(&mut context, &twap).await;
+    assert_eq!(twap_account.last_timestamp, clock.unix_timestamp + 100);
+    assert_eq!(twap_account.cumulative_price, u128::from(twap_account.average().unwrap()) * 100);
+}
+
+#[tokio::test]
+async fn mint_with_price_rejected_by_twap() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        price_mode: PriceMode::LessFavourable,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let twap_price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_price: 3_000_000,
+        ..PriceConfig::default()
+    };
+    let create_twap = create_twap_instruction(&payer_wallet.pubkey(), &c98_feed, 600, twap_price_config);
+    let poke = poke_instruction(&c98_feed, Vec::new());
+    process_transaction(&mut context, &Vec::from([create_twap, poke]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // mint records prices in the TWAP only once its price config accepts them, as poke does
+    set_pyth_price_account(&mut context, &c98_feed, 500_000_000, 0, -8, clock.unix_timestamp);
+    let (twap, _): (Pubkey, u8) = find_twap_address(&c98_feed);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account, twap]);
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceOutOfBounds))),
+    );
+}
+
+#[tokio::test]
+async fn poke_with_price_out_of_bounds() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        price_mode: PriceMode::Twap,
+        ..PriceConfig::default()
+    };
+    setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    // poke records only prices the TWAP price config accepts
+    let twap_price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_price: 2_000_000,
+        ..PriceConfig::default()
+    };
+    let create_twap = create_twap_instruction(&payer_wallet.pubkey(), &c98_feed, 600, twap_price_config);
+    process_transaction(&mut context, &Vec::from([create_twap]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let poke = poke_instruction(&c98_feed, Vec::new());
+    let result = try_process_transaction(&mut context, &Vec::from([poke]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::PriceOutOfBounds))),
+    );
+}
+
+#[tokio::test]
+async fn quote_mint_without_moving_funds() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let quote = quote_mint_instruction(&setup.minter, 1_000_000_000, Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([quote]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let minter: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert_eq!(minter.total_minted_amount, 0);
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 0);
+    assert_eq!(get_token_balance(&mut context, &setup.payer_cusd_token_account).await, 0);
+}
+
+#[tokio::test]
+async fn quote_mint_with_price_deviation() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        max_deviation: 500,
+        deviation_window: 3600,
+        pause_on_deviation: true,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    set_pyth_price_account(&mut context, &c98_feed, 200_000_000, 0, -8, clock.unix_timestamp);
+
+    // a quote reports the error mint would fail with, the minter is only paused through the breaker
+    let quote = quote_mint_instruction(&setup.minter, 500_000_000, remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([quote]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::CircuitBreakerTripped))),
+    );
+
+    let minter: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert!(minter.is_active);
+}
+
+#[tokio::test]
+async fn quote_mint_over_limit_with_stale_price() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp - 61);
+    let remaining_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    // limits are checked before prices on mint, the quote reports the same error
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 2_000_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), remaining_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::LimitReached))),
+    );
+
+    let quote = quote_mint_instruction(&setup.minter, 2_000_000_000_000, remaining_accounts);
+    let result = try_process_transaction(&mut context, &Vec::from([quote]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::LimitReached))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_invalid_token_account() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    // the price feed is not owned by the token program
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 0, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidTokenAccount))),
+    );
+}
+
+#[tokio::test]
+async fn mint_exact_input_with_math_overflow() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    let mint = mint_exact_input_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, u64::MAX, Vec::from([0, 1, 2]), 0, Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::MathOverflow))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_account_index_out_of_range() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    // the pool token account index points past the remaining accounts
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 7]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidAccount))),
+    );
+
+    // the account indices of the token are missing
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 500_000_000, Vec::from([0]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidAccount))),
+    );
+}
+
+#[tokio::test]
+async fn mint_with_insufficient_balance() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+
+    // a user without any c98, the token program rejects the transfer
+    let user_wallet = Keypair::new();
+    airdrop(&mut context, &user_wallet.pubkey(), 1_000_000_000).await.unwrap();
+    let user_c98_token_account = create_associated_token_account(&mut context, &user_wallet.pubkey(), &setup.c98_mint).await.unwrap();
+    let user_cusd_token_account = create_associated_token_account(&mut context, &user_wallet.pubkey(), &setup.cusd_mint).await.unwrap();
+
+    let mint = mint_instruction(&user_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &user_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, user_c98_token_account, setup.pool_c98_token_account]));
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&user_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::CpiFailed))),
+    );
+}
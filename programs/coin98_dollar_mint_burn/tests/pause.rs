@@ -0,0 +1,115 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use solana_program_test::*;
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::{AppData, OracleType, PriceConfig, Role};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+use utils::pyth::*;
+use utils::fixture::*;
+
+#[tokio::test]
+async fn pauser_pauses_mint_and_burn() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    let pauser = Keypair::new();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let minter = &setup.minter;
+    let mint_accounts = Vec::from([c98_feed, minter.payer_c98_token_account, minter.pool_c98_token_account]);
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account]);
+
+    let grant_pauser = grant_role_instruction(&payer_wallet.pubkey(), Role::Pauser, &pauser.pubkey());
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), mint_accounts.clone());
+    process_transaction(&mut context, &Vec::from([grant_pauser, mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // mint only, burn keeps working
+    let pause = pause_instruction(&pauser.pubkey(), false, true, false);
+    process_transaction(&mut context, &Vec::from([pause]), &Vec::from([&payer_wallet, &pauser])).await.unwrap();
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 500_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), mint_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Paused))),
+    );
+
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts.clone(), 100, 250_000_000);
+    process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // raising the burn flag keeps the mint flag raised
+    let pause = pause_instruction(&pauser.pubkey(), false, false, true);
+    process_transaction(&mut context, &Vec::from([pause]), &Vec::from([&payer_wallet, &pauser])).await.unwrap();
+
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let app_data_account: AppData = get_anchor_account(&mut context, &app_data).await;
+    assert!(!app_data_account.is_paused);
+    assert!(app_data_account.is_mint_paused);
+    assert!(app_data_account.is_burn_paused);
+
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 50, 125_000_000);
+    let result = try_process_transaction(&mut context, &Vec::from([burn]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Paused))),
+    );
+}
+
+#[tokio::test]
+async fn pauser_cannot_unpause() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    let pauser = Keypair::new();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let mint_accounts = Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]);
+
+    let grant_pauser = grant_role_instruction(&payer_wallet.pubkey(), Role::Pauser, &pauser.pubkey());
+    let pause = pause_instruction(&pauser.pubkey(), true, false, false);
+    process_transaction(&mut context, &Vec::from([grant_pauser, pause]), &Vec::from([&payer_wallet, &pauser])).await.unwrap();
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 500_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), mint_accounts.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Paused))),
+    );
+
+    // lowering flags is reserved for admins
+    let set_pause = set_pause_instruction(&pauser.pubkey(), false, false, false);
+    let result = try_process_transaction(&mut context, &Vec::from([set_pause]), &Vec::from([&payer_wallet, &pauser])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let set_pause = set_pause_instruction(&payer_wallet.pubkey(), false, false, false);
+    process_transaction(&mut context, &Vec::from([set_pause]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), mint_accounts);
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    assert_eq!(get_token_balance(&mut context, &setup.pool_c98_token_account).await, 400);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signer,
+};
+use solana_program_test::*;
+use coin98_dollar_mint_burn::event::{BurnEvent, MintEvent};
+use coin98_dollar_mint_burn::return_data::{BurnResult, MintResult};
+use coin98_dollar_mint_burn::state::{OracleType, PriceConfig};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+use utils::pyth::*;
+use utils::fixture::*;
+use utils::probe::*;
+
+#[tokio::test]
+async fn mint_sets_result_and_event() {
+    capture_events();
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let record = create_return_data_record(&mut context, &payer_wallet).await;
+
+    let mint = mint_instruction(&payer_wallet.pubkey(), &setup.cusd_mint, &setup.minter, &setup.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, setup.payer_c98_token_account, setup.pool_c98_token_account]));
+    let probe = return_data_probe_instruction(&record, ReturnDataKind::Mint, mint);
+    process_transaction(&mut context, &Vec::from([probe]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let result: MintResult = get_return_data_record(&mut context, &record).await;
+    assert_eq!(result, MintResult {
+        input_amounts: Vec::from([400]),
+        input_prices: Vec::from([250_000_000]),
+        input_precisions: Vec::from([100_000_000]),
+        amount_out: 990_000_000,
+        fee: 10_000_000,
+    });
+
+    let events: Vec<MintEvent> = get_events::<MintEvent>().into_iter()
+        .filter(|event| event.minter == setup.minter)
+        .collect();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.user, payer_wallet.pubkey());
+    assert_eq!(event.recipient, setup.payer_cusd_token_account);
+    assert_eq!(event.amount, 1_000_000_000);
+    assert_eq!(event.fee, 10_000_000);
+    assert_eq!(event.input_amounts, result.input_amounts);
+    assert_eq!(event.input_prices, result.input_prices);
+    assert_eq!(event.input_precisions, result.input_precisions);
+    assert_eq!(event.total_minted_amount, 1_000_000_000);
+    assert_eq!(event.per_period_minted_amount, 1_000_000_000);
+}
+
+#[tokio::test]
+async fn burn_sets_result_and_event() {
+    capture_events();
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 250_000_000, 0, -8, clock.unix_timestamp);
+    let record = create_return_data_record(&mut context, &payer_wallet).await;
+
+    let minter = &setup.minter;
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, minter.payer_c98_token_account, minter.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account, setup.fee_c98_token_account]), 100, 250_000_000);
+    let probe = return_data_probe_instruction(&record, ReturnDataKind::Burn, burn);
+    process_transaction(&mut context, &Vec::from([probe]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let result: BurnResult = get_return_data_record(&mut context, &record).await;
+    assert_eq!(result, BurnResult {
+        cusd_amount_in: 250_000_000,
+        amount_out: 99,
+        output_price: 250_000_000,
+        output_precision: 100_000_000,
+        fee: 1,
+    });
+
+    let events: Vec<BurnEvent> = get_events::<BurnEvent>().into_iter()
+        .filter(|event| event.burner == setup.burner)
+        .collect();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.user, payer_wallet.pubkey());
+    assert_eq!(event.recipient, minter.payer_c98_token_account);
+    assert_eq!(event.cusd_amount, 250_000_000);
+    assert_eq!(event.output_amount, 100);
+    assert_eq!(event.output_price, result.output_price);
+    assert_eq!(event.output_precision, result.output_precision);
+    assert_eq!(event.fee, 1);
+    assert_eq!(event.total_burned_amount, 250_000_000);
+}
+
+#[tokio::test]
+async fn quote_burn_matches_burn() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_burner(&mut context, &payer_wallet, &c98_feed, price_config, 100).await;
+    let clock = get_clock(&mut context).await;
+    set_pyth_price_account(&mut context, &c98_feed, 233_333_333, 1_000_000, -8, clock.unix_timestamp);
+    let record = create_return_data_record(&mut context, &payer_wallet).await;
+
+    let minter = &setup.minter;
+    let mint = mint_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &minter.minter, &minter.payer_cusd_token_account, 1_000_000_000, Vec::from([0, 1, 2]), Vec::from([1_000]), Vec::from([c98_feed, minter.payer_c98_token_account, minter.pool_c98_token_account]));
+    process_transaction(&mut context, &Vec::from([mint]), &Vec::from([&payer_wallet])).await.unwrap();
+    let burn_accounts = Vec::from([c98_feed, minter.pool_c98_token_account, minter.payer_c98_token_account, setup.fee_c98_token_account]);
+
+    let quote = quote_burn_instruction(&setup.burner, 123, burn_accounts.clone());
+    let probe = return_data_probe_instruction(&record, ReturnDataKind::Burn, quote);
+    process_transaction(&mut context, &Vec::from([probe]), &Vec::from([&payer_wallet])).await.unwrap();
+    let quote_result: BurnResult = get_return_data_record(&mut context, &record).await;
+
+    let burn = burn_instruction(&payer_wallet.pubkey(), &minter.cusd_mint, &setup.burner, &setup.pool_cusd_token_account, &minter.payer_cusd_token_account, burn_accounts, 123, quote_result.cusd_amount_in);
+    let probe = return_data_probe_instruction(&record, ReturnDataKind::Burn, burn);
+    process_transaction(&mut context, &Vec::from([probe]), &Vec::from([&payer_wallet])).await.unwrap();
+    let burn_result: BurnResult = get_return_data_record(&mut context, &record).await;
+
+    assert_eq!(quote_result, burn_result);
+    assert_eq!(get_token_balance(&mut context, &minter.payer_cusd_token_account).await, 1_000_000_000 - quote_result.cusd_amount_in);
+    assert_eq!(get_token_balance(&mut context, &setup.fee_c98_token_account).await, quote_result.fee);
+}
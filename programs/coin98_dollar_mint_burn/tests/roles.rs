@@ -0,0 +1,174 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use solana_program_test::*;
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::{AdminRegistry, Operation, PriceConfig, Role};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+
+#[tokio::test]
+async fn config_manager_cannot_queue_withdraw_token() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let config_manager = Keypair::new();
+    let treasurer = Keypair::new();
+    airdrop(&mut context, &config_manager.pubkey(), 1_000_000_000).await.unwrap();
+    airdrop(&mut context, &treasurer.pubkey(), 1_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &config_manager.pubkey());
+    let grant_treasurer = grant_role_instruction(&payer_wallet.pubkey(), Role::Treasurer, &treasurer.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager, grant_treasurer]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // moving funds out of the pools is reserved to treasurers
+    let operation = Operation::WithdrawToken {
+        pool_token: Pubkey::new_unique(),
+        recipient_token: Pubkey::new_unique(),
+        amount: 1_000,
+    };
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let queue_operation = queue_operation_instruction(&config_manager.pubkey(), operation_path, operation.clone());
+    let result = try_process_transaction(&mut context, &Vec::from([queue_operation]), &Vec::from([&payer_wallet, &config_manager])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let queue_operation = queue_operation_instruction(&treasurer.pubkey(), operation_path, operation);
+    process_transaction(&mut context, &Vec::from([queue_operation]), &Vec::from([&payer_wallet, &treasurer])).await.unwrap();
+}
+
+#[tokio::test]
+async fn pauser_cannot_set_minter() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let pauser = Keypair::new();
+    airdrop(&mut context, &pauser.pubkey(), 1_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    let grant_pauser = grant_role_instruction(&payer_wallet.pubkey(), Role::Pauser, &pauser.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager, grant_pauser]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let minter_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (minter, _) = find_minter_address(&minter_path);
+    let create_minter = create_minter_instruction(&payer_wallet.pubkey(), minter_path);
+    process_transaction(&mut context, &Vec::from([create_minter]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let set_minter = set_minter_instruction(&pauser.pubkey(), &minter, true, Vec::from([Pubkey::new_unique()]), Vec::from([6]), Vec::from([10000]), Vec::from([Pubkey::new_unique()]), Vec::from([PriceConfig::default()]), 0, 0, 0, 0);
+    let result = try_process_transaction(&mut context, &Vec::from([set_minter]), &Vec::from([&payer_wallet, &pauser])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let pause = pause_instruction(&pauser.pubkey(), false, true, false);
+    process_transaction(&mut context, &Vec::from([pause]), &Vec::from([&payer_wallet, &pauser])).await.unwrap();
+}
+
+#[tokio::test]
+async fn admin_without_role() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // admins manage the registry, every other instruction needs its role granted first
+    let minter_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let create_minter = create_minter_instruction(&payer_wallet.pubkey(), minter_path);
+    let result = try_process_transaction(&mut context, &Vec::from([create_minter]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let pause = pause_instruction(&payer_wallet.pubkey(), true, false, false);
+    let result = try_process_transaction(&mut context, &Vec::from([pause]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetTimelockDelay {
+        timelock_delay: 172_800,
+    });
+    let result = try_process_transaction(&mut context, &Vec::from([queue_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+}
+
+#[tokio::test]
+async fn revoke_role() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let pauser = Keypair::new();
+    airdrop(&mut context, &pauser.pubkey(), 1_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_pauser = grant_role_instruction(&payer_wallet.pubkey(), Role::Pauser, &pauser.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_pauser]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // only admins revoke roles, a member cannot drop its own
+    let revoke_pauser = revoke_role_instruction(&pauser.pubkey(), Role::Pauser, &pauser.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([revoke_pauser]), &Vec::from([&payer_wallet, &pauser])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let revoke_pauser = revoke_role_instruction(&payer_wallet.pubkey(), Role::Pauser, &pauser.pubkey());
+    process_transaction(&mut context, &Vec::from([revoke_pauser]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert!(!registry.has_role(Role::Pauser, &pauser.pubkey()));
+
+    let pause = pause_instruction(&pauser.pubkey(), true, false, false);
+    let result = try_process_transaction(&mut context, &Vec::from([pause]), &Vec::from([&payer_wallet, &pauser])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    // revoking a role the key does not hold is rejected
+    let revoke_treasurer = revoke_role_instruction(&payer_wallet.pubkey(), Role::Treasurer, &pauser.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([revoke_treasurer]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+
+    // the only signer cannot be revoked while the threshold needs one approval
+    let revoke_signer = revoke_role_instruction(&payer_wallet.pubkey(), Role::Signer, &payer_wallet.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([revoke_signer]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+}
@@ -0,0 +1,413 @@
+#![cfg(feature = "unit-test")]
+pub mod utils;
+
+pub use solana_sdk::{
+    pubkey::Pubkey,
+    account::{Account, AccountSharedData},
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use solana_program_test::*;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use coin98_dollar_mint_burn::constant::{DEFAULT_MAX_PRICE_AGE, DEFAULT_TIMELOCK_DELAY};
+use coin98_dollar_mint_burn::error::ErrorCode;
+use coin98_dollar_mint_burn::state::{AdminRegistry, AppData, LegacyAppData, Minter, MinterConfig, OracleType, Operation, PriceConfig, Role};
+use utils::helper::*;
+use utils::wallet::*;
+use utils::instructions::*;
+use utils::fixture::*;
+
+#[tokio::test]
+async fn execute_operation_before_eta() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (timelock_operation, _) = find_timelock_operation_address(&operation_path);
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetMinterLimits {
+        minter: setup.minter,
+        total_minted_limit: 2_000_000_000_000u64,
+        per_period_minted_limit: 2_000_000_000_000u64,
+    });
+    process_transaction(&mut context, &Vec::from([queue_operation]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let clock = get_clock(&mut context).await;
+    set_unix_timestamp(&mut context, clock.unix_timestamp + i64::from(DEFAULT_TIMELOCK_DELAY) - 1).await;
+
+    let execute_operation = execute_operation_instruction(&timelock_operation, Vec::from([setup.minter]));
+    let result = try_process_transaction(&mut context, &Vec::from([execute_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::TimelockNotExpired))),
+    );
+}
+
+#[tokio::test]
+async fn execute_operation_without_accounts() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (timelock_operation, _) = find_timelock_operation_address(&operation_path);
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetMinterLimits {
+        minter: setup.minter,
+        total_minted_limit: 2_000_000_000_000u64,
+        per_period_minted_limit: 2_000_000_000_000u64,
+    });
+    let approve_operation = approve_operation_instruction(&payer_wallet.pubkey(), &timelock_operation);
+    process_transaction(&mut context, &Vec::from([queue_operation, approve_operation]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let clock = get_clock(&mut context).await;
+    set_unix_timestamp(&mut context, clock.unix_timestamp + i64::from(DEFAULT_TIMELOCK_DELAY)).await;
+
+    // the minter is not passed as remaining account
+    let execute_operation = execute_operation_instruction(&timelock_operation, Vec::new());
+    let result = try_process_transaction(&mut context, &Vec::from([execute_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidAccount))),
+    );
+}
+
+#[tokio::test]
+async fn cancel_queued_operation() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (timelock_operation, _) = find_timelock_operation_address(&operation_path);
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetMinterLimits {
+        minter: setup.minter,
+        total_minted_limit: 2_000_000_000_000u64,
+        per_period_minted_limit: 2_000_000_000_000u64,
+    });
+    let grant_pauser = grant_role_instruction(&payer_wallet.pubkey(), Role::Pauser, &payer_wallet.pubkey());
+    let cancel_operation = cancel_operation_instruction(&payer_wallet.pubkey(), &timelock_operation);
+    process_transaction(&mut context, &Vec::from([queue_operation, grant_pauser, cancel_operation]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let clock = get_clock(&mut context).await;
+    set_unix_timestamp(&mut context, clock.unix_timestamp + i64::from(DEFAULT_TIMELOCK_DELAY)).await;
+
+    let execute_operation = execute_operation_instruction(&timelock_operation, Vec::from([setup.minter]));
+    let result = try_process_transaction(&mut context, &Vec::from([execute_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::OperationFinalized))),
+    );
+
+    let minter: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert_eq!(minter.total_minted_limit, 1_000_000_000_000u64);
+}
+
+#[tokio::test]
+async fn set_minter_fee_through_timelock() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config.clone(), 0).await;
+
+    // fees of a configured minter cannot change instantly
+    let set_minter = set_minter_instruction(&payer_wallet.pubkey(), &setup.minter, true, Vec::from([setup.c98_mint]), Vec::from([0]), Vec::from([10000]), Vec::from([c98_feed]), Vec::from([price_config.clone()]), 100, 1_000_000_000_000u64, 1_000_000_000_000u64, 0);
+    let result = try_process_transaction(&mut context, &Vec::from([set_minter]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::TimelockRequired))),
+    );
+
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::SetMinter {
+        minter: setup.minter,
+        config: MinterConfig {
+            is_active: true,
+            input_tokens: Vec::from([setup.c98_mint]),
+            input_decimals: Vec::from([0]),
+            input_percentages: Vec::from([10000]),
+            input_price_feeds: Vec::from([c98_feed]),
+            input_price_configs: Vec::from([price_config]),
+            fee_percent: 100,
+            total_minted_limit: 1_000_000_000_000u64,
+            per_period_minted_limit: 1_000_000_000_000u64,
+            min_amount: 0,
+        },
+    }, Vec::from([setup.minter])).await;
+
+    let minter: Minter = get_anchor_account(&mut context, &setup.minter).await;
+    assert_eq!(minter.fee_percent, 100);
+}
+
+#[tokio::test]
+async fn withdraw_token_is_deprecated() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config, 0).await;
+
+    // reserved to treasurers like Operation::WithdrawToken
+    let withdraw_token = withdraw_token_instruction(&payer_wallet.pubkey(), &setup.pool_c98_token_account, &setup.payer_c98_token_account, 0);
+    let result = try_process_transaction(&mut context, &Vec::from([withdraw_token]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::Unauthorized))),
+    );
+
+    let grant_treasurer = grant_role_instruction(&payer_wallet.pubkey(), Role::Treasurer, &payer_wallet.pubkey());
+    let withdraw_token = withdraw_token_instruction(&payer_wallet.pubkey(), &setup.pool_c98_token_account, &setup.payer_c98_token_account, 1);
+    let result = try_process_transaction(&mut context, &Vec::from([grant_treasurer, withdraw_token]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(1, InstructionError::Custom(u32::from(ErrorCode::TimelockRequired))),
+    );
+}
+
+#[tokio::test]
+async fn migrate_legacy_app_data() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let (app_data, nonce): (Pubkey, u8) = find_app_data_address();
+    let (_, signer_nonce): (Pubkey, u8) = find_root_signer_address();
+    let legacy_app_data = LegacyAppData {
+        nonce,
+        signer_nonce,
+        limit: 48,
+    };
+    let mut data = AppData::discriminator().to_vec();
+    data.extend(legacy_app_data.try_to_vec().unwrap());
+    data.resize(16 + LegacyAppData::LEN, 0);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: coin98_dollar_mint_burn::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&app_data, &AccountSharedData::from(account));
+
+    // the registry is bootstrapped first, its admin runs the migration
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let migrate_app_data = migrate_app_data_instruction(&payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, migrate_app_data]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let app_data_account: AppData = get_anchor_account(&mut context, &app_data).await;
+    assert_eq!(app_data_account.nonce, nonce);
+    assert_eq!(app_data_account.signer_nonce, signer_nonce);
+    assert_eq!(app_data_account.limit, 48);
+    assert_eq!(app_data_account.timelock_delay, DEFAULT_TIMELOCK_DELAY);
+    assert_eq!(app_data_account.max_price_age, DEFAULT_MAX_PRICE_AGE);
+    assert_eq!(app_data_account.treasury, payer_wallet.pubkey());
+
+    // a second migration would overwrite the new fields
+    let migrate_app_data = migrate_app_data_instruction(&payer_wallet.pubkey());
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 48, DEFAULT_TIMELOCK_DELAY, DEFAULT_MAX_PRICE_AGE);
+    let result = try_process_transaction(&mut context, &Vec::from([migrate_app_data, set_app_data]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+}
+
+#[tokio::test]
+async fn execute_operation_with_threshold() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let second_signer = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey(), second_signer.pubkey()]), 2);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (timelock_operation, _) = find_timelock_operation_address(&operation_path);
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetTimelockDelay {
+        timelock_delay: 2 * DEFAULT_TIMELOCK_DELAY,
+    });
+    let approve_operation = approve_operation_instruction(&payer_wallet.pubkey(), &timelock_operation);
+    process_transaction(&mut context, &Vec::from([queue_operation, approve_operation]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let clock = get_clock(&mut context).await;
+    set_unix_timestamp(&mut context, clock.unix_timestamp + i64::from(DEFAULT_TIMELOCK_DELAY)).await;
+
+    // one approval out of two
+    let execute_operation = execute_operation_instruction(&timelock_operation, Vec::new());
+    let result = try_process_transaction(&mut context, &Vec::from([execute_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::ThresholdNotMet))),
+    );
+
+    let approve_operation = approve_operation_instruction(&second_signer.pubkey(), &timelock_operation);
+    let execute_operation = execute_operation_instruction(&timelock_operation, Vec::new());
+    process_transaction(&mut context, &Vec::from([approve_operation, execute_operation]), &Vec::from([&payer_wallet, &second_signer])).await.unwrap();
+
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let app_data: AppData = get_anchor_account(&mut context, &app_data).await;
+    assert_eq!(app_data.timelock_delay, 2 * DEFAULT_TIMELOCK_DELAY);
+}
+
+#[tokio::test]
+async fn create_admin_registry_without_threshold() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 0);
+    let result = try_process_transaction(&mut context, &Vec::from([create_admin_registry]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+}
+
+#[tokio::test]
+async fn grant_signer_through_timelock() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+    let new_signer = Keypair::new();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    let grant_signer = grant_role_instruction(&payer_wallet.pubkey(), Role::Signer, &new_signer.pubkey());
+    let result = try_process_transaction(&mut context, &Vec::from([grant_signer]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::TimelockRequired))),
+    );
+
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::GrantSigner {
+        member: new_signer.pubkey(),
+    }, Vec::new()).await;
+
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let registry: AdminRegistry = get_anchor_account(&mut context, &admin_registry).await;
+    assert_eq!(registry.signers, Vec::from([payer_wallet.pubkey(), new_signer.pubkey()]));
+}
+
+#[tokio::test]
+async fn loosen_app_data_through_timelock() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+    airdrop(&mut context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    process_transaction(&mut context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    // a zero period would turn the per period limits into per transaction limits
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 0, DEFAULT_TIMELOCK_DELAY, DEFAULT_MAX_PRICE_AGE);
+    let result = try_process_transaction(&mut context, &Vec::from([set_app_data]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::TimelockRequired))),
+    );
+
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 24, DEFAULT_TIMELOCK_DELAY, DEFAULT_MAX_PRICE_AGE + 1);
+    let result = try_process_transaction(&mut context, &Vec::from([set_app_data]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::TimelockRequired))),
+    );
+
+    // tightening applies right away
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 48, DEFAULT_TIMELOCK_DELAY, 60);
+    process_transaction(&mut context, &Vec::from([set_app_data]), &Vec::from([&payer_wallet])).await.unwrap();
+
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::SetLimit {
+        limit: 0,
+    }, Vec::new()).await;
+    execute_timelocked_operation(&mut context, &payer_wallet, Operation::SetMaxPriceAge {
+        max_price_age: DEFAULT_MAX_PRICE_AGE,
+    }, Vec::new()).await;
+
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let app_data: AppData = get_anchor_account(&mut context, &app_data).await;
+    assert_eq!(app_data.limit, 0);
+    assert_eq!(app_data.max_price_age, DEFAULT_MAX_PRICE_AGE);
+}
+
+#[tokio::test]
+async fn queue_operation_with_invalid_arguments() {
+    let mut context = coin98_dollar_mint_burn_program_test().start_with_context().await;
+    let payer_wallet = get_default_wallet().unwrap();
+
+    let c98_feed = Pubkey::new_unique();
+    let price_config = PriceConfig {
+        oracle_type: OracleType::Pyth,
+        ..PriceConfig::default()
+    };
+    let setup = setup_pyth_minter(&mut context, &payer_wallet, &c98_feed, price_config.clone(), 0).await;
+
+    // percentages of the collaterals must add up to 100%
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetMinter {
+        minter: setup.minter,
+        config: MinterConfig {
+            is_active: true,
+            input_tokens: Vec::from([setup.c98_mint]),
+            input_decimals: Vec::from([0]),
+            input_percentages: Vec::from([5000]),
+            input_price_feeds: Vec::from([c98_feed]),
+            input_price_configs: Vec::from([price_config]),
+            fee_percent: 0,
+            total_minted_limit: 1_000_000_000_000u64,
+            per_period_minted_limit: 1_000_000_000_000u64,
+            min_amount: 0,
+        },
+    });
+    let result = try_process_transaction(&mut context, &Vec::from([queue_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+
+    // only one signer is registered
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, Operation::SetThreshold {
+        threshold: 2,
+    });
+    let result = try_process_transaction(&mut context, &Vec::from([queue_operation]), &Vec::from([&payer_wallet])).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(u32::from(ErrorCode::InvalidInput))),
+    );
+}
@@ -0,0 +1,139 @@
+use solana_program_test::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use coin98_dollar_mint_burn::constant::DEFAULT_TIMELOCK_DELAY;
+use coin98_dollar_mint_burn::state::{AppData, OracleType, Operation, PriceConfig, PriceFeed, Role};
+use crate::utils::helper::*;
+use crate::utils::instructions::*;
+
+pub struct PythMinter {
+    pub c98_mint: Pubkey,
+    pub cusd_mint: Pubkey,
+    pub minter: Pubkey,
+    pub payer_cusd_token_account: Pubkey,
+    pub payer_c98_token_account: Pubkey,
+    pub pool_c98_token_account: Pubkey,
+}
+
+// Single collateral minter priced by c98_feed, the payer is admin, config manager and only signer
+// The clock has moved past the timelock delay on return, so prices must be set afterwards
+pub async fn setup_pyth_minter(
+    context: &mut ProgramTestContext,
+    payer_wallet: &Keypair,
+    c98_feed: &Pubkey,
+    price_config: PriceConfig,
+    fee_percent: u16,
+) -> PythMinter {
+    airdrop(context, &payer_wallet.pubkey(), 10_000_000_000).await.unwrap();
+
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+
+    let c98_mint = Keypair::new();
+    create_mint(context, &c98_mint, &payer_wallet.pubkey(), None).await.unwrap();
+    let cusd_mint = Keypair::from_bytes(&[202,192,162,73,184,144,236,61,88,204,128,42,118,116,110,72,153,114,57,183,67,59,239,160,46,130,112,92,219,145,116,21,171,46,92,155,111,121,107,137,187,201,219,116,208,23,156,137,19,146,184,45,122,164,241,252,184,1,174,7,13,160,189,174]).unwrap();
+    create_mint(context, &cusd_mint, &root_signer, Some(&root_signer)).await.unwrap();
+
+    let payer_c98_token_account = create_associated_token_account(context, &payer_wallet.pubkey(), &c98_mint.pubkey()).await.unwrap();
+    let pool_c98_token_account = create_associated_token_account(context, &root_signer, &c98_mint.pubkey()).await.unwrap();
+    let payer_cusd_token_account = create_associated_token_account(context, &payer_wallet.pubkey(), &cusd_mint.pubkey()).await.unwrap();
+    mint_tokens(context, &c98_mint.pubkey(), &payer_c98_token_account, 1_000_000_000_000, &payer_wallet.pubkey(), Some(payer_wallet)).await.unwrap();
+
+    let create_admin_registry = create_admin_registry_instruction(&payer_wallet.pubkey(), Vec::from([payer_wallet.pubkey()]), 1);
+    let create_app_data = create_app_data_instruction(&payer_wallet.pubkey());
+    let grant_config_manager = grant_role_instruction(&payer_wallet.pubkey(), Role::ConfigManager, &payer_wallet.pubkey());
+    let set_app_data = set_app_data_instruction(&payer_wallet.pubkey(), 24, DEFAULT_TIMELOCK_DELAY, 60);
+    process_transaction(context, &Vec::from([create_admin_registry, create_app_data, grant_config_manager, set_app_data]), &Vec::from([payer_wallet])).await.unwrap();
+
+    let minter_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (minter, _) = find_minter_address(&minter_path);
+    let create_minter = create_minter_instruction(&payer_wallet.pubkey(), minter_path);
+    let set_minter = set_minter_instruction(&payer_wallet.pubkey(), &minter, true, Vec::from([c98_mint.pubkey()]), Vec::from([0]), Vec::from([10000]), Vec::from([*c98_feed]), Vec::from([price_config]), fee_percent, 0, 0, 0);
+    process_transaction(context, &Vec::from([create_minter, set_minter]), &Vec::from([payer_wallet])).await.unwrap();
+    execute_timelocked_operation(context, payer_wallet, Operation::SetMinterLimits {
+        minter,
+        total_minted_limit: 1_000_000_000_000u64,
+        per_period_minted_limit: 1_000_000_000_000u64,
+    }, Vec::from([minter])).await;
+
+    PythMinter {
+        c98_mint: c98_mint.pubkey(),
+        cusd_mint: cusd_mint.pubkey(),
+        minter,
+        payer_cusd_token_account,
+        payer_c98_token_account,
+        pool_c98_token_account,
+    }
+}
+
+pub struct PythBurner {
+    pub minter: PythMinter,
+    pub burner: Pubkey,
+    pub pool_cusd_token_account: Pubkey,
+    pub fee_c98_token_account: Pubkey,
+}
+
+// Minter of setup_pyth_minter along with a burner paying out c98 from a funded pool,
+// burn fees are kept aside in fee_c98_token_account
+pub async fn setup_pyth_burner(
+    context: &mut ProgramTestContext,
+    payer_wallet: &Keypair,
+    c98_feed: &Pubkey,
+    price_config: PriceConfig,
+    fee_percent: u16,
+) -> PythBurner {
+    let minter = setup_pyth_minter(context, payer_wallet, c98_feed, price_config.clone(), 0).await;
+
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+    let (fee_signer, _): (Pubkey, u8) = find_fee_signer_address();
+    let pool_cusd_token_account = create_associated_token_account(context, &root_signer, &minter.cusd_mint).await.unwrap();
+    let fee_c98_token_account = create_associated_token_account(context, &fee_signer, &minter.c98_mint).await.unwrap();
+    mint_tokens(context, &minter.c98_mint, &minter.pool_c98_token_account, 1_000_000_000_000, &payer_wallet.pubkey(), Some(payer_wallet)).await.unwrap();
+
+    let burner_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (burner, _) = find_burner_address(&burner_path);
+    let create_burner = create_burner_instruction(&payer_wallet.pubkey(), burner_path);
+    let set_burner = set_burner_instruction(&payer_wallet.pubkey(), &burner, true, minter.c98_mint, 0, *c98_feed, price_config, fee_percent, 0, 0, 0);
+    process_transaction(context, &Vec::from([create_burner, set_burner]), &Vec::from([payer_wallet])).await.unwrap();
+    execute_timelocked_operation(context, payer_wallet, Operation::SetBurnerLimits {
+        burner,
+        total_burned_limit: 1_000_000_000_000u64,
+        per_period_burned_limit: 1_000_000_000_000u64,
+    }, Vec::from([burner])).await;
+
+    PythBurner {
+        minter,
+        burner,
+        pool_cusd_token_account,
+        fee_c98_token_account,
+    }
+}
+
+// Queues and approves the operation as the only signer, waits out the timelock delay and executes it,
+// prices read before are stale afterwards
+pub async fn execute_timelocked_operation(
+    context: &mut ProgramTestContext,
+    payer_wallet: &Keypair,
+    operation: Operation,
+    accounts: Vec<Pubkey>,
+) {
+    let operation_path: Vec<u8> = (0..10).map(|_| { rand::random::<u8>() }).collect();
+    let (timelock_operation, _) = find_timelock_operation_address(&operation_path);
+    let queue_operation = queue_operation_instruction(&payer_wallet.pubkey(), operation_path, operation);
+    let approve_operation = approve_operation_instruction(&payer_wallet.pubkey(), &timelock_operation);
+    process_transaction(context, &Vec::from([queue_operation, approve_operation]), &Vec::from([payer_wallet])).await.unwrap();
+
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let app_data: AppData = get_anchor_account(context, &app_data).await;
+    let clock = get_clock(context).await;
+    set_unix_timestamp(context, clock.unix_timestamp + i64::from(app_data.timelock_delay)).await;
+
+    let execute_operation = execute_operation_instruction(&timelock_operation, accounts);
+    process_transaction(context, &Vec::from([execute_operation]), &Vec::from([payer_wallet])).await.unwrap();
+}
+
+pub fn pyth_feed(address: &Pubkey) -> PriceFeed {
+    PriceFeed {
+        oracle_type: OracleType::Pyth,
+        address: *address,
+    }
+}
@@ -7,10 +7,13 @@ use solana_sdk::signature::Keypair;
 use solana_sdk::program_pack::Pack;
 use solana_program::instruction::Instruction;
 use solana_sdk::system_instruction;
+use solana_sdk::clock::Clock;
+use crate::utils::probe::*;
 
 pub fn coin98_dollar_mint_burn_program_test() -> ProgramTest {
     let mut program = ProgramTest::new("coin98_dollar_mint_burn", coin98_dollar_mint_burn::id(), None);
     program.add_program("chainlink_dfeed", chainlink_dfeed::id(), None);
+    program.add_program("return_data_probe", RETURN_DATA_PROBE_ID, processor!(process_return_data_probe));
     program
 }
 
@@ -51,6 +54,40 @@ pub async fn process_transaction(context: &mut ProgramTestContext, instructions:
     Ok(())
 }
 
+pub async fn try_process_transaction(context: &mut ProgramTestContext, instructions: &Vec<Instruction>, signers: &Vec<&Keypair>) -> Result<(), TransportError> {
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&signers[0].pubkey()),
+        signers,
+        context.last_blockhash,
+    );
+
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+}
+
+pub async fn get_clock(context: &mut ProgramTestContext) -> Clock {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap()
+}
+
+pub async fn set_unix_timestamp(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock = get_clock(context).await;
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+pub async fn get_token_balance(context: &mut ProgramTestContext, token_account: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*token_account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+pub async fn get_anchor_account<T: anchor_lang::AccountDeserialize>(context: &mut ProgramTestContext, address: &Pubkey) -> T {
+    let account = context.banks_client.get_account(*address).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
 pub async fn create_mint(
     context: &mut ProgramTestContext,
     mint: &Keypair,
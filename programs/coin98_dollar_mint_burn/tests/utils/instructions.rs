@@ -3,11 +3,18 @@ use anchor_lang::solana_program::system_program;
 use solana_program::instruction::{Instruction, AccountMeta};
 use solana_sdk::pubkey::Pubkey;
 use coin98_dollar_mint_burn::constant::{
+    ADMIN_REGISTRY_SEED_1,
+    ADMIN_REGISTRY_SEED_2,
     APP_DATA_SEED_1,
     APP_DATA_SEED_2,
+    FEE_SIGNER_SEED_1,
+    FEE_SIGNER_SEED_2,
     ROOT_SIGNER_SEED_1,
-    ROOT_SIGNER_SEED_2
+    ROOT_SIGNER_SEED_2,
+    TIMELOCK_OPERATION_SEED,
+    TWAP_SEED
 };
+use coin98_dollar_mint_burn::state::{Operation, PriceConfig, Role};
 
 const MINTER_SEEDS: &[u8] = &[121, 44, 123, 235, 166, 175, 64, 142];
 const BURNER_SEEDS: &[u8] = &[240, 112, 187, 250, 94, 126, 188, 74];
@@ -15,10 +22,12 @@ const BURNER_SEEDS: &[u8] = &[240, 112, 187, 250, 94, 126, 188, 74];
 pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey::new_from_array([6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237, 95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169]);
 
 pub fn create_minter_instruction(root: &Pubkey, path: Vec<u8>) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let (minter, _): (Pubkey, u8) = find_minter_address(&path);
 
     let accounts = coin98_dollar_mint_burn::accounts::CreateMinterContext {
         root: *root,
+        admin_registry,
         minter,
         system_program: system_program::id()
     }.to_account_metas(None);
@@ -45,13 +54,16 @@ pub fn set_minter_instruction(
     input_decimals: Vec<u16>,
     input_percentages: Vec<u16>,
     input_price_feeds: Vec<Pubkey>,
+    input_price_configs: Vec<PriceConfig>,
     fee_percent: u16,
     total_minted_limit: u64,
     per_period_minted_limit: u64,
     min_amount: u64
 ) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let accounts = coin98_dollar_mint_burn::accounts::SetMinterContext {
         root: *root,
+        admin_registry,
         minter: *minter,
     }.to_account_metas(None);
 
@@ -61,6 +73,7 @@ pub fn set_minter_instruction(
         input_decimals,
         input_percentages,
         input_price_feeds,
+        input_price_configs,
         fee_percent,
         total_minted_limit,
         per_period_minted_limit,
@@ -84,6 +97,7 @@ pub fn mint_instruction(
     recipient: &Pubkey,
     amount: u64,
     extra_instructions: Vec<u8>,
+    max_input_amounts: Vec<u64>,
     extra_accounts: Vec<Pubkey>
 ) -> Instruction {
     let (app_data, _): (Pubkey, u8) = find_app_data_address();
@@ -106,7 +120,52 @@ pub fn mint_instruction(
 
     let data = coin98_dollar_mint_burn::instruction::Mint {
         amount,
-        extra_instructions
+        extra_instructions,
+        max_input_amounts
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn mint_exact_input_instruction(
+    user: &Pubkey, 
+    cusd_mint: &Pubkey,
+    minter: &Pubkey,
+    recipient: &Pubkey,
+    input_amount: u64,
+    extra_instructions: Vec<u8>,
+    min_amount_out: u64,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::MintContext {
+        user: *user,
+        app_data,
+        root_signer,
+        cusd_mint: *cusd_mint,
+        minter: *minter,
+        recipient: *recipient,
+        chainlink_program: chainlink_dfeed::id(),
+        token_program: TOKEN_PROGRAM_ID
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::MintExactInput {
+        input_amount,
+        extra_instructions,
+        min_amount_out
     }
     .data();
 
@@ -120,10 +179,12 @@ pub fn mint_instruction(
 }
 
 pub fn create_burner_instruction(root: &Pubkey, path: Vec<u8>) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let (burner, _): (Pubkey, u8) = find_burner_address(&path);
 
     let accounts = coin98_dollar_mint_burn::accounts::CreateBurnerContext {
         root: *root,
+        admin_registry,
         burner,
         system_program: system_program::id()
     }.to_account_metas(None);
@@ -149,13 +210,16 @@ pub fn set_burner_instruction(
     output_token: Pubkey,
     output_decimals: u16,
     output_price_feed: Pubkey,
+    output_price_config: PriceConfig,
     fee_percent: u16,
     total_burned_limit: u64,
     per_period_burned_limit: u64,
     min_amount: u64
 ) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let accounts = coin98_dollar_mint_burn::accounts::SetBurnerContext {
         root: *root,
+        admin_registry,
         burner: *burner,
     }.to_account_metas(None);
 
@@ -164,6 +228,7 @@ pub fn set_burner_instruction(
         output_token,
         output_decimals,
         output_price_feed,
+        output_price_config,
         fee_percent,
         total_burned_limit,
         per_period_burned_limit,
@@ -180,6 +245,124 @@ pub fn set_burner_instruction(
     instruction
 }
 
+pub fn quote_mint_instruction(
+    minter: &Pubkey,
+    amount: u64,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::QuoteMintContext {
+        app_data,
+        minter: *minter,
+        chainlink_program: chainlink_dfeed::id(),
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new_readonly(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::QuoteMint {
+        amount
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn quote_burn_instruction(
+    burner: &Pubkey,
+    amount: u64,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::QuoteBurnContext {
+        app_data,
+        burner: *burner,
+        chainlink_program: chainlink_dfeed::id(),
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new_readonly(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::QuoteBurn {
+        amount
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn trip_minter_circuit_breaker_instruction(
+    minter: &Pubkey,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::TripMinterCircuitBreakerContext {
+        app_data,
+        minter: *minter,
+        chainlink_program: chainlink_dfeed::id(),
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new_readonly(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::TripMinterCircuitBreaker {}
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn trip_burner_circuit_breaker_instruction(
+    burner: &Pubkey,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::TripBurnerCircuitBreakerContext {
+        app_data,
+        burner: *burner,
+        chainlink_program: chainlink_dfeed::id(),
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new_readonly(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::TripBurnerCircuitBreaker {}
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
 pub fn burn_instruction(
     user: &Pubkey, 
     cusd_mint: &Pubkey,
@@ -187,7 +370,8 @@ pub fn burn_instruction(
     pool_cusd: &Pubkey,
     user_cusd: &Pubkey,
     extra_accounts: Vec<Pubkey>,
-    amount: u64
+    amount: u64,
+    max_cusd_in: u64
 ) -> Instruction {
     let (app_data, _): (Pubkey, u8) = find_app_data_address();
     let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
@@ -209,7 +393,52 @@ pub fn burn_instruction(
     }
 
     let data = coin98_dollar_mint_burn::instruction::Burn {
-        amount
+        amount,
+        max_cusd_in
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn burn_cusd_instruction(
+    user: &Pubkey, 
+    cusd_mint: &Pubkey,
+    burner: &Pubkey,
+    pool_cusd: &Pubkey,
+    user_cusd: &Pubkey,
+    extra_accounts: Vec<Pubkey>,
+    cusd_amount: u64,
+    min_amount_out: u64
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::BurnContext {
+        user: *user,
+        app_data,
+        root_signer,
+        cusd_mint: *cusd_mint,
+        burner: *burner,
+        pool_cusd: *pool_cusd,
+        user_cusd: *user_cusd,
+        chainlink_program: chainlink_dfeed::id(),
+        token_program: TOKEN_PROGRAM_ID
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::BurnCusd {
+        cusd_amount,
+        min_amount_out
     }
     .data();
 
@@ -226,10 +455,12 @@ pub fn burn_instruction(
 pub fn create_app_data_instruction(
     root: &Pubkey
 ) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let (app_data, _): (Pubkey, u8) = find_app_data_address();
 
     let accounts = coin98_dollar_mint_burn::accounts::CreateAppDataContext {
         root: *root,
+        admin_registry,
         app_data,
         system_program: system_program::id()
     }.to_account_metas(None);
@@ -247,19 +478,100 @@ pub fn create_app_data_instruction(
     instruction
 }
 
-pub fn set_app_data_instruction(
+pub fn create_admin_registry_instruction(
     root: &Pubkey,
-    limit: u32
+    signers: Vec<Pubkey>,
+    threshold: u8
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::CreateAdminRegistryContext {
+        root: *root,
+        admin_registry,
+        system_program: system_program::id()
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::CreateAdminRegistry {
+        signers,
+        threshold
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn migrate_app_data_instruction(
+    root: &Pubkey
 ) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let (app_data, _): (Pubkey, u8) = find_app_data_address();
 
-    let accounts = coin98_dollar_mint_burn::accounts::SetAppDataContext {
+    let accounts = coin98_dollar_mint_burn::accounts::MigrateAppDataContext {
         root: *root,
+        admin_registry,
         app_data,
+        system_program: system_program::id()
     }.to_account_metas(None);
 
-    let data = coin98_dollar_mint_burn::instruction::SetAppData {
-        limit
+    let data = coin98_dollar_mint_burn::instruction::MigrateAppData {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn migrate_minter_instruction(
+    root: &Pubkey,
+    minter: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::MigrateMinterContext {
+        root: *root,
+        admin_registry,
+        minter: *minter,
+        system_program: system_program::id()
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::MigrateMinter {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn migrate_burner_instruction(
+    root: &Pubkey,
+    burner: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::MigrateBurnerContext {
+        root: *root,
+        admin_registry,
+        burner: *burner,
+        system_program: system_program::id()
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::MigrateBurner {
     }
     .data();
 
@@ -278,11 +590,13 @@ pub fn withdraw_token_instruction(
     recipient_token: &Pubkey,
     amount: u64
 ) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let (app_data, _): (Pubkey, u8) = find_app_data_address();
     let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
 
     let accounts = coin98_dollar_mint_burn::accounts::WithdrawTokenContext {
         root: *root,
+        admin_registry,
         app_data,
         root_signer,
         pool_token: *pool_token,
@@ -291,7 +605,7 @@ pub fn withdraw_token_instruction(
     }.to_account_metas(None);
 
     let data = coin98_dollar_mint_burn::instruction::WithdrawToken {
-        amount,
+        _amount: amount
     }
     .data();
 
@@ -304,22 +618,25 @@ pub fn withdraw_token_instruction(
     instruction
 }
 
-pub fn unlock_token_mint_instruction(
+pub fn set_app_data_instruction(
     root: &Pubkey,
-    token_mint: &Pubkey
+    limit: u32,
+    timelock_delay: u32,
+    max_price_age: u32
 ) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
     let (app_data, _): (Pubkey, u8) = find_app_data_address();
-    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
 
-    let accounts = coin98_dollar_mint_burn::accounts::UnlockTokenMintContext {
+    let accounts = coin98_dollar_mint_burn::accounts::SetAppDataContext {
         root: *root,
+        admin_registry,
         app_data,
-        root_signer,
-        token_mint: *token_mint,
-        token_program: TOKEN_PROGRAM_ID
     }.to_account_metas(None);
 
-    let data = coin98_dollar_mint_burn::instruction::UnlockTokenMint {
+    let data = coin98_dollar_mint_burn::instruction::SetAppData {
+        limit,
+        timelock_delay,
+        max_price_age
     }
     .data();
 
@@ -332,16 +649,483 @@ pub fn unlock_token_mint_instruction(
     instruction
 }
 
-pub fn find_minter_address(path: &Vec<u8>) -> (Pubkey, u8) {
-    let seeds = &[MINTER_SEEDS, path];
-    Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
-}
-
+pub fn pause_instruction(
+    root: &Pubkey,
+    is_paused: bool,
+    is_mint_paused: bool,
+    is_burn_paused: bool
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::PauseContext {
+        root: *root,
+        admin_registry,
+        app_data,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::Pause {
+        is_paused,
+        is_mint_paused,
+        is_burn_paused
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn set_pause_instruction(
+    root: &Pubkey,
+    is_paused: bool,
+    is_mint_paused: bool,
+    is_burn_paused: bool
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::SetPauseContext {
+        root: *root,
+        admin_registry,
+        app_data,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::SetPause {
+        is_paused,
+        is_mint_paused,
+        is_burn_paused
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn add_admin_instruction(
+    root: &Pubkey,
+    admin: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::AddAdminContext {
+        root: *root,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::AddAdmin {
+        admin: *admin,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn remove_admin_instruction(
+    root: &Pubkey,
+    admin: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::RemoveAdminContext {
+        root: *root,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::RemoveAdmin {
+        admin: *admin,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn propose_admin_instruction(
+    root: &Pubkey,
+    pending_admin: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::ProposeAdminContext {
+        root: *root,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::ProposeAdmin {
+        pending_admin: *pending_admin,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn accept_admin_instruction(
+    pending_admin: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::AcceptAdminContext {
+        pending_admin: *pending_admin,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::AcceptAdmin {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn cancel_admin_transfer_instruction(
+    root: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::CancelAdminTransferContext {
+        root: *root,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::CancelAdminTransfer {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn grant_role_instruction(
+    root: &Pubkey,
+    role: Role,
+    member: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::GrantRoleContext {
+        root: *root,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::GrantRole {
+        role,
+        member: *member,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn revoke_role_instruction(
+    root: &Pubkey,
+    role: Role,
+    member: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::RevokeRoleContext {
+        root: *root,
+        admin_registry,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::RevokeRole {
+        role,
+        member: *member,
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn queue_operation_instruction(
+    root: &Pubkey,
+    path: Vec<u8>,
+    operation: Operation
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let (timelock_operation, _): (Pubkey, u8) = find_timelock_operation_address(&path);
+
+    let accounts = coin98_dollar_mint_burn::accounts::QueueOperationContext {
+        root: *root,
+        admin_registry,
+        app_data,
+        timelock_operation,
+        system_program: system_program::id()
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::QueueOperation {
+        _derivation_path: path,
+        operation
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn approve_operation_instruction(
+    root: &Pubkey,
+    timelock_operation: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::ApproveOperationContext {
+        root: *root,
+        admin_registry,
+        timelock_operation: *timelock_operation,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::ApproveOperation {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn execute_operation_instruction(
+    timelock_operation: &Pubkey,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::ExecuteOperationContext {
+        admin_registry,
+        app_data,
+        root_signer,
+        timelock_operation: *timelock_operation,
+        token_program: TOKEN_PROGRAM_ID
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::ExecuteOperation {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn cancel_operation_instruction(
+    root: &Pubkey,
+    timelock_operation: &Pubkey
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+
+    let accounts = coin98_dollar_mint_burn::accounts::CancelOperationContext {
+        root: *root,
+        admin_registry,
+        timelock_operation: *timelock_operation,
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::CancelOperation {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn create_twap_instruction(
+    root: &Pubkey,
+    price_feed: &Pubkey,
+    window: u32,
+    price_config: PriceConfig
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let (twap, _): (Pubkey, u8) = find_twap_address(price_feed);
+
+    let accounts = coin98_dollar_mint_burn::accounts::CreateTwapContext {
+        root: *root,
+        admin_registry,
+        price_feed: *price_feed,
+        twap,
+        system_program: system_program::id()
+    }.to_account_metas(None);
+
+    let data = coin98_dollar_mint_burn::instruction::CreateTwap {
+        window,
+        price_config
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn poke_instruction(
+    price_feed: &Pubkey,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let (twap, _): (Pubkey, u8) = find_twap_address(price_feed);
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::PokeContext {
+        app_data,
+        twap,
+        price_feed: *price_feed,
+        chainlink_program: chainlink_dfeed::id()
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new_readonly(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::Poke {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn collect_fees_instruction(
+    root: &Pubkey,
+    cusd_mint: &Pubkey,
+    extra_accounts: Vec<Pubkey>
+) -> Instruction {
+    let (admin_registry, _): (Pubkey, u8) = find_admin_registry_address();
+    let (app_data, _): (Pubkey, u8) = find_app_data_address();
+    let (root_signer, _): (Pubkey, u8) = find_root_signer_address();
+    let (fee_signer, _): (Pubkey, u8) = find_fee_signer_address();
+
+    let mut accounts = coin98_dollar_mint_burn::accounts::CollectFeesContext {
+        root: *root,
+        admin_registry,
+        app_data,
+        root_signer,
+        fee_signer,
+        cusd_mint: *cusd_mint,
+        token_program: TOKEN_PROGRAM_ID
+    }.to_account_metas(None);
+
+    for account in extra_accounts.iter() {
+        accounts.push(AccountMeta::new(*account, false));
+    }
+
+    let data = coin98_dollar_mint_burn::instruction::CollectFees {
+    }
+    .data();
+
+    let instruction = Instruction {
+        program_id: coin98_dollar_mint_burn::id(),
+        data,
+        accounts
+    };
+
+    instruction
+}
+
+pub fn find_minter_address(path: &Vec<u8>) -> (Pubkey, u8) {
+    let seeds = &[MINTER_SEEDS, path];
+    Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
+}
+
 pub fn find_burner_address(path: &Vec<u8>) -> (Pubkey, u8) {
     let seeds = &[BURNER_SEEDS, path];
     Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
 }
 
+pub fn find_timelock_operation_address(path: &Vec<u8>) -> (Pubkey, u8) {
+    let seeds = &[TIMELOCK_OPERATION_SEED, path];
+    Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
+}
+
+pub fn find_twap_address(price_feed: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[TWAP_SEED, price_feed.as_ref()];
+    Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
+}
+
+pub fn find_admin_registry_address() -> (Pubkey, u8) {
+    let seeds = &[ADMIN_REGISTRY_SEED_1, ADMIN_REGISTRY_SEED_2];
+    Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
+}
+
 pub fn find_app_data_address() -> (Pubkey, u8) {
     let seeds = &[APP_DATA_SEED_1, APP_DATA_SEED_2];
     Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
@@ -351,3 +1135,8 @@ pub fn find_root_signer_address() -> (Pubkey, u8) {
     let seeds = &[ROOT_SIGNER_SEED_1, ROOT_SIGNER_SEED_2];
     Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
 }
+
+pub fn find_fee_signer_address() -> (Pubkey, u8) {
+    let seeds = &[FEE_SIGNER_SEED_1, FEE_SIGNER_SEED_2];
+    Pubkey::find_program_address(seeds, &coin98_dollar_mint_burn::id())
+}
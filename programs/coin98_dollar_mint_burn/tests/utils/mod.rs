@@ -1,4 +1,7 @@
 pub mod wallet;
 pub mod helper;
 pub mod chainlink;
+pub mod pyth;
 pub mod instructions;
+pub mod fixture;
+pub mod probe;
@@ -0,0 +1,135 @@
+use std::sync::Mutex;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize, Event};
+use coin98_dollar_mint_burn::return_data::{get_burn_result, get_mint_result};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
+use solana_program::program_error::ProgramError;
+use solana_program_test::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use crate::utils::helper::*;
+
+pub const RETURN_DATA_PROBE_ID: Pubkey = Pubkey::new_from_array([84, 19, 203, 7, 152, 61, 240, 33, 118, 9, 171, 226, 90, 47, 135, 208, 12, 164, 99, 58, 231, 140, 26, 77, 193, 5, 110, 248, 67, 181, 42, 150]);
+pub const RETURN_DATA_RECORD_SIZE: usize = 512;
+
+// Result the probe decodes from the return data of the wrapped instruction
+#[derive(Clone, Copy)]
+pub enum ReturnDataKind {
+    Mint,
+    Burn,
+}
+
+// Builtin invoking the instruction wrapped by return_data_probe_instruction, the return data set by
+// the callee is decoded the way a calling program would, with get_mint_result or get_burn_result,
+// and copied into the record account prefixed with its length
+pub fn process_return_data_probe(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let record = accounts.get(0).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let program = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (kind, data) = data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+    let instruction = Instruction {
+        program_id: *program.key,
+        accounts: accounts[2..].iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    invoke(&instruction, &accounts[1..])?;
+
+    let return_data = if *kind == ReturnDataKind::Mint as u8 {
+        get_mint_result().and_then(|result| result.try_to_vec().ok())
+    } else {
+        get_burn_result().and_then(|result| result.try_to_vec().ok())
+    };
+    let return_data = return_data.ok_or(ProgramError::InvalidInstructionData)?;
+    let mut record_data = record.try_borrow_mut_data()?;
+    record_data[..4].copy_from_slice(&(return_data.len() as u32).to_le_bytes());
+    record_data[4..4 + return_data.len()].copy_from_slice(&return_data);
+
+    Ok(())
+}
+
+pub fn return_data_probe_instruction(record: &Pubkey, kind: ReturnDataKind, instruction: Instruction) -> Instruction {
+    let mut accounts = Vec::from([
+        AccountMeta::new(*record, false),
+        AccountMeta::new_readonly(instruction.program_id, false),
+    ]);
+    accounts.extend(instruction.accounts);
+    let mut data = Vec::from([kind as u8]);
+    data.extend(instruction.data);
+
+    Instruction {
+        program_id: RETURN_DATA_PROBE_ID,
+        data,
+        accounts
+    }
+}
+
+pub async fn create_return_data_record(context: &mut ProgramTestContext, payer_wallet: &Keypair) -> Pubkey {
+    let record = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_account = system_instruction::create_account(
+        &payer_wallet.pubkey(),
+        &record.pubkey(),
+        rent.minimum_balance(RETURN_DATA_RECORD_SIZE),
+        RETURN_DATA_RECORD_SIZE as u64,
+        &RETURN_DATA_PROBE_ID,
+    );
+    process_transaction(context, &Vec::from([create_account]), &Vec::from([payer_wallet, &record])).await.unwrap();
+
+    record.pubkey()
+}
+
+pub async fn get_return_data_record<T: AnchorDeserialize>(context: &mut ProgramTestContext, record: &Pubkey) -> T {
+    let account = context.banks_client.get_account(*record).await.unwrap().unwrap();
+    let mut length = [0u8; 4];
+    length.copy_from_slice(&account.data[..4]);
+    let length = u32::from_le_bytes(length) as usize;
+    T::try_from_slice(&account.data[4..4 + length]).unwrap()
+}
+
+static PROGRAM_DATA: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct ProgramDataLogger;
+
+impl log::Log for ProgramDataLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let message = record.args().to_string();
+        if let Some(data) = message.strip_prefix("Program data: ") {
+            PROGRAM_DATA.lock().unwrap().push(data.to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+// Records the "Program data" logs written by emit!, must run before the first ProgramTest is created
+// since solana_logger keeps a logger installed earlier, tests of the binary share the records
+pub fn capture_events() {
+    if log::set_logger(&ProgramDataLogger).is_ok() {
+        log::set_max_level(log::LevelFilter::Debug);
+    }
+}
+
+pub fn get_events<T: Event>() -> Vec<T> {
+    PROGRAM_DATA.lock().unwrap().iter()
+        .flat_map(|data| data.split(' ').map(str::to_string).collect::<Vec<String>>())
+        .filter_map(|data| base64::decode(data).ok())
+        .filter(|data| data.len() >= 8 && data[..8] == T::discriminator())
+        .filter_map(|data| T::try_from_slice(&data[8..]).ok())
+        .collect()
+}
@@ -0,0 +1,43 @@
+use coin98_dollar_mint_burn::external::pyth;
+use solana_program_test::*;
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::pubkey::Pubkey;
+
+pub fn pyth_price_account_data(
+    price: i64,
+    conf: u64,
+    expo: i32,
+    status: u32,
+    timestamp: i64,
+) -> Vec<u8> {
+    let mut data = vec![0u8; pyth::PRICE_ACCOUNT_LEN];
+    data[0..4].copy_from_slice(&pyth::MAGIC.to_le_bytes());
+    data[4..8].copy_from_slice(&pyth::VERSION.to_le_bytes());
+    data[8..12].copy_from_slice(&pyth::ACCOUNT_TYPE_PRICE.to_le_bytes());
+    data[12..16].copy_from_slice(&(pyth::PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+    data[20..24].copy_from_slice(&expo.to_le_bytes());
+    data[96..104].copy_from_slice(&timestamp.to_le_bytes());
+    data[208..216].copy_from_slice(&price.to_le_bytes());
+    data[216..224].copy_from_slice(&conf.to_le_bytes());
+    data[224..228].copy_from_slice(&status.to_le_bytes());
+    data
+}
+
+pub fn set_pyth_price_account(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+    price: i64,
+    conf: u64,
+    expo: i32,
+    timestamp: i64,
+) {
+    let data = pyth_price_account_data(price, conf, expo, pyth::PRICE_STATUS_TRADING, timestamp);
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: pyth::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(address, &AccountSharedData::from(account));
+}
@@ -12,17 +12,23 @@ import {
 } from '@solana/web3.js';
 import BN from 'bn.js';
 import {
+  AdminRegistry,
+  AppData,
   Burner,
   CusdFactoryInstructionService,
   InputTokenPair,
   InputTokenParams,
   Minter,
+  Operation,
   OutputTokenPair,
-  OutputTokenParams
+  OutputTokenParams,
+  Role,
+  TimelockOperation
 } from './cusd_factory_instruction.service';
 
 export class CusdFactoryService {
 
+  // Limits start at zero, raising them must be queued as a SetMinterLimits operation
   static async createMinter(
     connection: Connection,
     payerAccount: Keypair,
@@ -30,8 +36,7 @@ export class CusdFactoryService {
     isActive: boolean,
     inputParams: InputTokenParams[],
     feePercent: number,
-    totalMintedLimit: BN,
-    perPeriodMintedLimit: BN,
+    minAmount: BN,
     cusdFactoryProgramId: PublicKey,
   ): Promise<PublicKey> {
 
@@ -56,8 +61,9 @@ export class CusdFactoryService {
       isActive,
       inputParams,
       feePercent,
-      totalMintedLimit,
-      perPeriodMintedLimit,
+      new BN(0),
+      new BN(0),
+      minAmount,
       cusdFactoryProgramId,
     )
     transaction.add(setMinterInstruction)
@@ -69,6 +75,7 @@ export class CusdFactoryService {
     return minterAddress
   }
 
+  // Only tightening applies right away, looser settings must be queued as an operation
   static async setMinter(
     connection: Connection,
     payerAccount: Keypair,
@@ -78,6 +85,7 @@ export class CusdFactoryService {
     feePercent: number,
     totalMintedLimit: BN,
     perPeriodMintedLimit: BN,
+    minAmount: BN,
     cusdFactoryProgramId: PublicKey,
   ): Promise<boolean> {
 
@@ -91,6 +99,7 @@ export class CusdFactoryService {
       feePercent,
       totalMintedLimit,
       perPeriodMintedLimit,
+      minAmount,
       cusdFactoryProgramId,
     )
     transaction.add(setMinterInstruction)
@@ -102,6 +111,7 @@ export class CusdFactoryService {
     return true
   }
 
+  // Limits start at zero, raising them must be queued as a SetBurnerLimits operation
   static async createBurner(
     connection: Connection,
     payerAccount: Keypair,
@@ -109,8 +119,7 @@ export class CusdFactoryService {
     isActive: boolean,
     outputParams: OutputTokenParams,
     feePercent: number,
-    totalBurnedLimit: BN,
-    perPeriodBurnedLimit: BN,
+    minAmount: BN,
     cusdFactoryProgramId: PublicKey,
   ): Promise<PublicKey> {
 
@@ -135,8 +144,9 @@ export class CusdFactoryService {
       isActive,
       outputParams,
       feePercent,
-      totalBurnedLimit,
-      perPeriodBurnedLimit,
+      new BN(0),
+      new BN(0),
+      minAmount,
       cusdFactoryProgramId,
     )
     transaction.add(setBurnerInstruction)
@@ -148,6 +158,7 @@ export class CusdFactoryService {
     return burnerAddress
   }
 
+  // Only tightening applies right away, looser settings must be queued as an operation
   static async setBurner(
     connection: Connection,
     payerAccount: Keypair,
@@ -157,6 +168,7 @@ export class CusdFactoryService {
     feePercent: number,
     totalBurnedLimit: BN,
     perPeriodBurnedLimit: BN,
+    minAmount: BN,
     cusdFactoryProgramId: PublicKey,
   ): Promise<boolean> {
 
@@ -170,6 +182,7 @@ export class CusdFactoryService {
       feePercent,
       totalBurnedLimit,
       perPeriodBurnedLimit,
+      minAmount,
       cusdFactoryProgramId,
     )
     transaction.add(setBurnerInstruction)
@@ -187,6 +200,7 @@ export class CusdFactoryService {
     minterAddress: PublicKey,
     cusdTokenMintAddress: PublicKey,
     amount: BN,
+    maxInputAmounts: BN[],
     userCusdTokenAddress: PublicKey,
     chainlinkProgramId: PublicKey,
     cusdFactoryProgramId: PublicKey,
@@ -207,6 +221,7 @@ export class CusdFactoryService {
 
     const inputTokens: InputTokenPair[] = minterInfo.inputTokens.map((tokenMintAddress, index) => {
       const priceFeedAddress = minterInfo.inputPriceFeeds[index]
+      const priceConfig = minterInfo.inputPriceConfigs[index]
       const poolTokenAddress = TokenProgramService.findAssociatedTokenAddress(
         rootSignerAddress,
         tokenMintAddress,
@@ -215,10 +230,16 @@ export class CusdFactoryService {
         payerAccount.publicKey,
         tokenMintAddress,
       )
+      const extraPriceFeedAddresses = priceConfig.extraPriceFeeds.map(priceFeed => priceFeed.address)
+      const twapAddress = priceConfig.priceMode.spot
+        ? null
+        : CusdFactoryInstructionService.findTwapAddress(priceFeedAddress, cusdFactoryProgramId)[0]
       return <InputTokenPair>{
         priceFeedAddress,
         poolTokenAddress,
         userTokenAddress,
+        extraPriceFeedAddresses,
+        twapAddress,
       }
     })
 
@@ -228,6 +249,7 @@ export class CusdFactoryService {
       cusdTokenMintAddress,
       inputTokens,
       amount,
+      maxInputAmounts,
       userCusdTokenAddress,
       chainlinkProgramId,
       cusdFactoryProgramId,
@@ -248,6 +270,7 @@ export class CusdFactoryService {
     cusdTokenMintAddress: PublicKey,
     userCusdTokenAddress: PublicKey,
     amount: BN,
+    maxCusdIn: BN,
     userTokenAddress: PublicKey,
     chainlinkProgramId: PublicKey,
     cusdFactoryProgramId: PublicKey,
@@ -270,10 +293,36 @@ export class CusdFactoryService {
       rootSignerAddress,
       burnerInfo.outputToken,
     )
+    const priceConfig = burnerInfo.outputPriceConfig
+    const twapAddress = priceConfig.priceMode.spot
+      ? null
+      : CusdFactoryInstructionService.findTwapAddress(burnerInfo.outputPriceFeed, cusdFactoryProgramId)[0]
+
+    // burn fees are moved to a token account of the fee signer
+    let feeTokenAddress: PublicKey | null = null
+    if (burnerInfo.feePercent > 0) {
+      const [feeSignerAddress,] = CusdFactoryInstructionService.findFeeSignerAddress(
+        cusdFactoryProgramId,
+      )
+      let [recipientTokenAddress, createATAInstruction] = await TokenProgramService.findRecipientTokenAddress(
+        connection,
+        payerAccount.publicKey,
+        feeSignerAddress,
+        burnerInfo.outputToken,
+      )
+      if(createATAInstruction) {
+        transaction.add(createATAInstruction)
+      }
+      feeTokenAddress = recipientTokenAddress
+    }
+
     const outputToken: OutputTokenPair = {
       priceFeedAddress: burnerInfo.outputPriceFeed,
       poolTokenAddress,
       userTokenAddress,
+      extraPriceFeedAddresses: priceConfig.extraPriceFeeds.map(priceFeed => priceFeed.address),
+      twapAddress,
+      feeTokenAddress,
     }
 
     const burnInstruction = CusdFactoryInstructionService.burn(
@@ -282,6 +331,7 @@ export class CusdFactoryService {
       cusdTokenMintAddress,
       userCusdTokenAddress,
       amount,
+      maxCusdIn,
       outputToken,
       chainlinkProgramId,
       cusdFactoryProgramId,
@@ -295,16 +345,47 @@ export class CusdFactoryService {
     return true
   }
 
-  static async withdrawToken(
+  static async queueOperation(
     connection: Connection,
     payerAccount: Keypair,
+    name: string,
+    operation: Operation,
+    cusdFactoryProgramId: PublicKey,
+  ): Promise<PublicKey> {
+
+    const derivationPath = HashService.sha256(name).slice(0, 8)
+
+    const transaction = new Transaction()
+
+    const queueOperationInstruction = CusdFactoryInstructionService.queueOperation(
+      payerAccount.publicKey,
+      derivationPath,
+      operation,
+      cusdFactoryProgramId,
+    )
+    transaction.add(queueOperationInstruction)
+
+    const [timelockOperationAddress,] = this.findTimelockOperationAddress(
+      derivationPath,
+      cusdFactoryProgramId,
+    )
+
+    const txSign = await sendTransaction(connection, transaction, [
+      payerAccount,
+    ])
+    console.info(`Queued Operation ${timelockOperationAddress.toBase58()}`, '---', txSign, '\n')
+    return timelockOperationAddress
+  }
+
+  static async queueWithdrawToken(
+    connection: Connection,
+    payerAccount: Keypair,
+    name: string,
     poolTokenAddress: PublicKey,
     recipientAddress: PublicKey,
     amount: BN,
     cusdFactoryProgramId: PublicKey,
-  ): Promise<boolean> {
-
-    const transaction = new Transaction()
+  ): Promise<PublicKey> {
 
     const payerTokenAccountInfo = await TokenProgramService.getTokenAccountInfo(
       connection,
@@ -317,61 +398,139 @@ export class CusdFactoryService {
       payerTokenAccountInfo.mint,
     )
     if(createATAInstruction) {
+      const transaction = new Transaction()
       transaction.add(createATAInstruction)
+      await sendTransaction(connection, transaction, [
+        payerAccount,
+      ])
     }
 
-    const withdrawInstruction = CusdFactoryInstructionService.withdrawToken(
+    return this.queueOperation(
+      connection,
+      payerAccount,
+      name,
+      <Operation>{
+        withdrawToken: {
+          poolToken: poolTokenAddress,
+          recipientToken: recipientTokenAddress,
+          amount,
+        },
+      },
+      cusdFactoryProgramId,
+    )
+  }
+
+  static async queueUnlockTokenMint(
+    connection: Connection,
+    payerAccount: Keypair,
+    name: string,
+    tokenMintAddress: PublicKey,
+    newAuthorityAddress: PublicKey,
+    cusdFactoryProgramId: PublicKey,
+  ): Promise<PublicKey> {
+
+    return this.queueOperation(
+      connection,
+      payerAccount,
+      name,
+      <Operation>{
+        unlockTokenMint: {
+          tokenMint: tokenMintAddress,
+          newAuthority: newAuthorityAddress,
+        },
+      },
+      cusdFactoryProgramId,
+    )
+  }
+
+  static async approveOperation(
+    connection: Connection,
+    payerAccount: Keypair,
+    timelockOperationAddress: PublicKey,
+    cusdFactoryProgramId: PublicKey,
+  ): Promise<boolean> {
+
+    const transaction = new Transaction()
+
+    const approveOperationInstruction = CusdFactoryInstructionService.approveOperation(
       payerAccount.publicKey,
-      poolTokenAddress,
-      recipientTokenAddress,
-      amount,
+      timelockOperationAddress,
       cusdFactoryProgramId,
     )
-    transaction.add(withdrawInstruction)
+    transaction.add(approveOperationInstruction)
 
     const txSign = await sendTransaction(connection, transaction, [
       payerAccount,
     ])
-    console.info(`Withdraw ${amount.toString()} tokens`, '---', txSign, '\n')
+    console.info(`Approved Operation ${timelockOperationAddress.toBase58()}`, '---', txSign, '\n')
     return true
   }
 
-  static async unlockTokenMint(
+  // Anyone can execute once the delay has passed and the signers threshold is met
+  static async executeOperation(
     connection: Connection,
     payerAccount: Keypair,
-    tokenMintAddress: PublicKey,
+    timelockOperationAddress: PublicKey,
     cusdFactoryProgramId: PublicKey,
   ): Promise<boolean> {
 
+    const timelockOperationInfo = await this.getTimelockOperationAccountInfo(
+      connection,
+      timelockOperationAddress,
+    )
+    if(timelockOperationInfo == null) {
+      return false
+    }
+
     const transaction = new Transaction()
 
-    const unlockTokenMintInstruction = CusdFactoryInstructionService.unlockTokenMint(
-      payerAccount.publicKey,
-      tokenMintAddress,
+    const executeOperationInstruction = CusdFactoryInstructionService.executeOperation(
+      timelockOperationAddress,
+      timelockOperationInfo.operation,
       cusdFactoryProgramId,
     )
-    transaction.add(unlockTokenMintInstruction)
+    transaction.add(executeOperationInstruction)
 
     const txSign = await sendTransaction(connection, transaction, [
       payerAccount,
     ])
-    console.info(`Token Mint ${tokenMintAddress.toBase58()}'s authority unlocked to owner address`, '---', txSign, '\n')
+    console.info(`Executed Operation ${timelockOperationAddress.toBase58()}`, '---', txSign, '\n')
     return true
   }
 
+  // Seats the payer as admin and only signer of a new registry, then grants it every other role
   static async initAppData(
     connection: Connection,
     payerAccount: Keypair,
     limit: number,
+    timelockDelay: number,
+    maxPriceAge: number,
     cusdFactoryProgramId: PublicKey,
   ): Promise<boolean> {
 
     const transaction = new Transaction()
 
+    const [adminRegistryAddress,] = CusdFactoryInstructionService.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
     const [appDataAddress,] = CusdFactoryInstructionService.findAppDataAddress(
       cusdFactoryProgramId,
     )
 
+    const adminRegistryInfo = await this.getAdminRegistryAccountInfo(
+      connection,
+      adminRegistryAddress,
+    )
+    if (adminRegistryInfo == null) {
+      const createAdminRegistryInstruction = CusdFactoryInstructionService.createAdminRegistry(
+        payerAccount.publicKey,
+        [payerAccount.publicKey],
+        1,
+        cusdFactoryProgramId,
+      )
+      transaction.add(createAdminRegistryInstruction)
+    }
+
     if (await SolanaService.isAddressAvailable(connection, appDataAddress)) {
       const createAppDataInstruction = CusdFactoryInstructionService.createAppData(
         payerAccount.publicKey,
@@ -380,9 +539,30 @@ export class CusdFactoryService {
       transaction.add(createAppDataInstruction)
     }
 
+    const roles: [Role, PublicKey[]][] = [
+      [{ configManager: {} }, adminRegistryInfo?.configManagers ?? []],
+      [{ pauser: {} }, adminRegistryInfo?.pausers ?? []],
+      [{ treasurer: {} }, adminRegistryInfo?.treasurers ?? []],
+      [{ mintAuthorityCustodian: {} }, adminRegistryInfo?.mintAuthorityCustodians ?? []],
+    ]
+    for (const [role, members] of roles) {
+      if (members.some(member => member.equals(payerAccount.publicKey))) {
+        continue
+      }
+      const grantRoleInstruction = CusdFactoryInstructionService.grantRole(
+        payerAccount.publicKey,
+        role,
+        payerAccount.publicKey,
+        cusdFactoryProgramId,
+      )
+      transaction.add(grantRoleInstruction)
+    }
+
     const setAppDataInstruction = CusdFactoryInstructionService.setAppData(
       payerAccount.publicKey,
       limit,
+      timelockDelay,
+      maxPriceAge,
       cusdFactoryProgramId,
     )
     transaction.add(setAppDataInstruction)
@@ -394,6 +574,30 @@ export class CusdFactoryService {
     return true
   }
 
+  static async getAppDataAccountInfo(
+    connection: Connection,
+    appDataAddress: PublicKey,
+  ): Promise<AppData | null> {
+    const accountInfo = await connection.getAccountInfo(appDataAddress)
+    if(accountInfo == null) {
+      return null
+    }
+    const data = CusdFactoryInstructionService.decodeAppDataData(accountInfo.data)
+    return data
+  }
+
+  static async getAdminRegistryAccountInfo(
+    connection: Connection,
+    adminRegistryAddress: PublicKey,
+  ): Promise<AdminRegistry | null> {
+    const accountInfo = await connection.getAccountInfo(adminRegistryAddress)
+    if(accountInfo == null) {
+      return null
+    }
+    const data = CusdFactoryInstructionService.decodeAdminRegistryData(accountInfo.data)
+    return data
+  }
+
   static async getMinterAccountInfo(
     connection: Connection,
     minterAddress: PublicKey,
@@ -418,6 +622,26 @@ export class CusdFactoryService {
     return data
   }
 
+  static async getTimelockOperationAccountInfo(
+    connection: Connection,
+    timelockOperationAddress: PublicKey,
+  ): Promise<TimelockOperation | null> {
+    const accountInfo = await connection.getAccountInfo(timelockOperationAddress)
+    if(accountInfo == null) {
+      return null
+    }
+    const data = CusdFactoryInstructionService.decodeTimelockOperationData(accountInfo.data)
+    return data
+  }
+
+  static findAdminRegistryAddress(
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return CusdFactoryInstructionService.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
+  }
+
   static findAppDataAddress(
     cusdFactoryProgramId: PublicKey,
   ): [PublicKey, number] {
@@ -434,6 +658,14 @@ export class CusdFactoryService {
     )
   }
 
+  static findFeeSignerAddress(
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return CusdFactoryInstructionService.findFeeSignerAddress(
+      cusdFactoryProgramId,
+    )
+  }
+
   static findMinterAddress(
     params: string | Buffer,
     cusdFactoryProgramId: PublicKey,
@@ -459,4 +691,27 @@ export class CusdFactoryService {
       cusdFactoryProgramId,
     )
   }
+
+  static findTimelockOperationAddress(
+    params: string | Buffer,
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    const derivationPath = (typeof(params) === 'string')
+      ? HashService.sha256(params).slice(0, 8)
+      : params
+    return CusdFactoryInstructionService.findTimelockOperationAddress(
+      derivationPath,
+      cusdFactoryProgramId,
+    )
+  }
+
+  static findTwapAddress(
+    priceFeedAddress: PublicKey,
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return CusdFactoryInstructionService.findTwapAddress(
+      priceFeedAddress,
+      cusdFactoryProgramId,
+    )
+  }
 }
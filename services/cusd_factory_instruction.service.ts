@@ -15,6 +15,13 @@ import CusdFactoryIdl from '../target/idl/coin98_dollar_mint_burn.json'
 
 const coder = new BorshCoder(CusdFactoryIdl as Idl)
 
+const ADMIN_REGISTRY_SEED_1 = Buffer.from([241, 130, 106, 126, 178, 232, 140, 4])
+const ADMIN_REGISTRY_SEED_2 = Buffer.from([3, 50, 193, 153, 232, 161, 192, 14])
+const FEE_SIGNER_SEED_1 = Buffer.from([91, 226, 17, 140, 63, 208, 121, 5])
+const FEE_SIGNER_SEED_2 = Buffer.from([174, 38, 250, 99, 12, 187, 46, 233])
+const TIMELOCK_OPERATION_SEED = Buffer.from([77, 18, 200, 31, 165, 9, 244, 130])
+const TWAP_SEED = Buffer.from([163, 87, 12, 210, 54, 199, 33, 118])
+
 // Requests
 interface CreateMinterRequest {
   derivationPath: Buffer
@@ -26,9 +33,11 @@ interface SetMinterRequest {
   inputDecimals: number[]
   inputPercentages: number[]
   inputPriceFeeds: PublicKey[]
+  inputPriceConfigs: PriceConfig[]
   feePercent: number
   totalMintedLimit: BN
   perPeriodMintedLimit: BN
+  minAmount: BN
 }
 
 interface CreateBurnerRequest {
@@ -40,32 +49,171 @@ interface SetBurnerRequest {
   outputToken: PublicKey
   outputDecimals: number
   outputPriceFeed: PublicKey
+  outputPriceConfig: PriceConfig
   feePercent: number
   totalBurnedLimit: BN
   perPeriodBurnedLimit: BN
+  minAmount: BN
 }
 
 interface MintRequest {
   amount: BN
   extraInstructions: Buffer
+  maxInputAmounts: BN[]
 }
 
 interface BurnRequest {
   amount: BN
+  maxCusdIn: BN
 }
 
-interface WithdrawTokenRequest {
-  amount: BN
-}
-
-interface UnlockTokenMintRequest {
+interface CreateAppDataRequest {
 }
 
-interface CreateAppDataRequest {
+interface CreateAdminRegistryRequest {
+  signers: PublicKey[]
+  threshold: number
 }
 
 interface SetAppDataRequest {
   limit: number
+  timelockDelay: number
+  maxPriceAge: number
+}
+
+interface GrantRoleRequest {
+  role: Role
+  member: PublicKey
+}
+
+interface QueueOperationRequest {
+  derivationPath: Buffer
+  operation: Operation
+}
+
+interface ApproveOperationRequest {
+}
+
+interface ExecuteOperationRequest {
+}
+
+// Types
+export interface Role {
+  configManager?: {}
+  pauser?: {}
+  treasurer?: {}
+  mintAuthorityCustodian?: {}
+  signer?: {}
+}
+
+export interface OracleType {
+  fixed?: {}
+  chainlink?: {}
+  pyth?: {}
+}
+
+export interface PriceMode {
+  spot?: {}
+  twap?: {}
+  lessFavourable?: {}
+}
+
+export interface PriceFeed {
+  oracleType: OracleType
+  address: PublicKey
+}
+
+export interface PriceConfig {
+  oracleType: OracleType
+  minPrice: BN
+  maxPrice: BN
+  extraPriceFeeds: PriceFeed[]
+  quorum: number
+  maxDivergence: number
+  maxDeviation: number
+  deviationWindow: number
+  pauseOnDeviation: boolean
+  maxPegDeviation: number
+  maxConfidenceRatio: number
+  priceMode: PriceMode
+}
+
+export interface AcceptedPrice {
+  price: BN
+  timestamp: BN
+}
+
+export interface MinterConfig {
+  isActive: boolean
+  inputTokens: PublicKey[]
+  inputDecimals: number[]
+  inputPercentages: number[]
+  inputPriceFeeds: PublicKey[]
+  inputPriceConfigs: PriceConfig[]
+  feePercent: number
+  totalMintedLimit: BN
+  perPeriodMintedLimit: BN
+  minAmount: BN
+}
+
+export interface BurnerConfig {
+  isActive: boolean
+  outputToken: PublicKey
+  outputDecimals: number
+  outputPriceFeed: PublicKey
+  outputPriceConfig: PriceConfig
+  feePercent: number
+  totalBurnedLimit: BN
+  perPeriodBurnedLimit: BN
+  minAmount: BN
+}
+
+export interface Operation {
+  withdrawToken?: {
+    poolToken: PublicKey
+    recipientToken: PublicKey
+    amount: BN
+  }
+  unlockTokenMint?: {
+    tokenMint: PublicKey
+    newAuthority: PublicKey
+  }
+  setMinterLimits?: {
+    minter: PublicKey
+    totalMintedLimit: BN
+    perPeriodMintedLimit: BN
+  }
+  setBurnerLimits?: {
+    burner: PublicKey
+    totalBurnedLimit: BN
+    perPeriodBurnedLimit: BN
+  }
+  setTimelockDelay?: {
+    timelockDelay: number
+  }
+  setThreshold?: {
+    threshold: number
+  }
+  setTreasury?: {
+    treasury: PublicKey
+  }
+  setMinter?: {
+    minter: PublicKey
+    config: MinterConfig
+  }
+  setBurner?: {
+    burner: PublicKey
+    config: BurnerConfig
+  }
+  grantSigner?: {
+    member: PublicKey
+  }
+  setLimit?: {
+    limit: number
+  }
+  setMaxPriceAge?: {
+    maxPriceAge: number
+  }
 }
 
 // Accounts
@@ -73,6 +221,26 @@ export interface AppData {
   nonce: number
   signerNonce: number
   limit: number
+  timelockDelay: number
+  isPaused: boolean
+  isMintPaused: boolean
+  isBurnPaused: boolean
+  maxPriceAge: number
+  treasury: PublicKey
+  feeSignerNonce: number
+}
+
+export interface AdminRegistry {
+  nonce: number
+  admins: PublicKey[]
+  pendingAdmin: PublicKey
+  pendingAdminProposer: PublicKey
+  configManagers: PublicKey[]
+  pausers: PublicKey[]
+  treasurers: PublicKey[]
+  mintAuthorityCustodians: PublicKey[]
+  signers: PublicKey[]
+  threshold: number
 }
 
 export interface Minter {
@@ -82,6 +250,8 @@ export interface Minter {
   inputDecimals: number[]
   inputPercentages: number[]
   inputPriceFeeds: PublicKey[]
+  inputPriceConfigs: PriceConfig[]
+  inputLastPrices: AcceptedPrice[]
   feePercent: number
   accumulatedFee: BN
   totalMintedAmount: BN
@@ -89,6 +259,7 @@ export interface Minter {
   perPeriodMintedAmount: BN
   perPeriodMintedLimit: BN
   lastPeriodTimestamp: BN
+  minAmount: BN
 }
 
 export interface Burner {
@@ -97,6 +268,8 @@ export interface Burner {
   outputToken: PublicKey
   outputDecimals: number
   outputPriceFeed: PublicKey
+  outputPriceConfig: PriceConfig
+  outputLastPrice: AcceptedPrice
   feePercent: number
   accumulatedFee: BN
   totalBurnedAmount: BN
@@ -104,6 +277,17 @@ export interface Burner {
   perPeriodBurnedAmount: BN
   perPeriodBurnedLimit: BN
   lastPeriodTimestamp: BN
+  minAmount: BN
+}
+
+export interface TimelockOperation {
+  nonce: number
+  proposer: PublicKey
+  operation: Operation
+  eta: BN
+  approvals: PublicKey[]
+  isExecuted: boolean
+  isCancelled: boolean
 }
 
 // Helpers
@@ -112,6 +296,8 @@ export interface InputTokenPair {
   priceFeedAddress: PublicKey
   poolTokenAddress: PublicKey
   userTokenAddress: PublicKey
+  extraPriceFeedAddresses: PublicKey[]
+  twapAddress: PublicKey | null
 }
 
 export interface InputTokenParams {
@@ -119,18 +305,23 @@ export interface InputTokenParams {
   decimals: number
   percentage: number
   priceFeedAddress: PublicKey
+  priceConfig: PriceConfig
 }
 
 export interface OutputTokenPair {
   priceFeedAddress: PublicKey
   poolTokenAddress: PublicKey
   userTokenAddress: PublicKey
+  extraPriceFeedAddresses: PublicKey[]
+  twapAddress: PublicKey | null
+  feeTokenAddress: PublicKey | null
 }
 
 export interface OutputTokenParams {
   tokenAddress: PublicKey
   decimals: number
   priceFeedAddress: PublicKey
+  priceConfig: PriceConfig
 }
 
 // RPC
@@ -148,6 +339,9 @@ export class CusdFactoryInstructionService {
 
     const data = coder.instruction.encode('createMinter', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
     const [minterAddress,] = this.findMinterAddress(
       derivationPath,
       cusdFactoryProgramId,
@@ -155,6 +349,7 @@ export class CusdFactoryInstructionService {
 
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: true },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
       <AccountMeta>{ pubkey: minterAddress, isSigner: false, isWritable: true },
       <AccountMeta>{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ]
@@ -174,6 +369,7 @@ export class CusdFactoryInstructionService {
     feePercent: number,
     totalMintedLimit: BN,
     perPeriodMintedLimit: BN,
+    minAmount: BN,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
@@ -181,21 +377,29 @@ export class CusdFactoryInstructionService {
     const inputDecimals = inputParams.map(param => param.decimals)
     const inputPercentages = inputParams.map(param => param.percentage)
     const inputPriceFeeds = inputParams.map(param => param.priceFeedAddress)
+    const inputPriceConfigs = inputParams.map(param => param.priceConfig)
     const request: SetMinterRequest = {
       isActive,
       inputTokens,
       inputDecimals,
       inputPercentages,
       inputPriceFeeds,
+      inputPriceConfigs,
       feePercent,
       totalMintedLimit,
       perPeriodMintedLimit,
+      minAmount,
     }
 
     const data = coder.instruction.encode('setMinter', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
+
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: false },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
       <AccountMeta>{ pubkey: minterAddress, isSigner: false, isWritable: true },
     ]
 
@@ -218,6 +422,9 @@ export class CusdFactoryInstructionService {
 
     const data = coder.instruction.encode('createBurner', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
     const [burnerAddress,] = this.findBurnerAddress(
       derivationPath,
       cusdFactoryProgramId,
@@ -225,6 +432,7 @@ export class CusdFactoryInstructionService {
 
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: true },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
       <AccountMeta>{ pubkey: burnerAddress, isSigner: false, isWritable: true },
       <AccountMeta>{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ]
@@ -244,6 +452,7 @@ export class CusdFactoryInstructionService {
     feePercent: number,
     totalBurnedLimit: BN,
     perPeriodBurnedLimit: BN,
+    minAmount: BN,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
@@ -252,15 +461,22 @@ export class CusdFactoryInstructionService {
       outputToken: outputParams.tokenAddress,
       outputDecimals: outputParams.decimals,
       outputPriceFeed: outputParams.priceFeedAddress,
+      outputPriceConfig: outputParams.priceConfig,
       feePercent,
       totalBurnedLimit,
       perPeriodBurnedLimit,
+      minAmount,
     }
 
     const data = coder.instruction.encode('setBurner', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
+
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: false },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
       <AccountMeta>{ pubkey: burnerAddress, isSigner: false, isWritable: true },
     ]
 
@@ -277,51 +493,49 @@ export class CusdFactoryInstructionService {
     cusdTokenMintAddress: PublicKey,
     inputTokens: InputTokenPair[],
     amount: BN,
+    maxInputAmounts: BN[],
     userCusdTokenAddress: PublicKey,
     chainlinkProgramId: PublicKey,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
+    // price feed, user token and pool token of every input token, then their extra feeds
+    // and the TWAP accounts of the tokens not priced at spot, both in token order
     let extraAccounts: AccountMeta[] = []
     let extraInstructions: number[] = []
     for (const inputToken of inputTokens) {
-      const index1 = extraAccounts.findIndex(meta => meta.pubkey.toBase58() === inputToken.priceFeedAddress.toBase58())
-      if (index1 === -1) {
-        extraInstructions.push(extraAccounts.length)
-        extraAccounts.push(
-          <AccountMeta>{ pubkey: inputToken.priceFeedAddress, isSigner: false, isWritable: false },
-        )
-      }
-      else {
-        extraInstructions.push(index1)
-      }
-
-      const index2 = extraAccounts.findIndex(meta => meta.pubkey.toBase58() === inputToken.userTokenAddress.toBase58())
-      if (index2 === -1) {
-        extraInstructions.push(extraAccounts.length)
+      extraInstructions.push(extraAccounts.length)
+      extraAccounts.push(
+        <AccountMeta>{ pubkey: inputToken.priceFeedAddress, isSigner: false, isWritable: false },
+      )
+      extraInstructions.push(extraAccounts.length)
+      extraAccounts.push(
+        <AccountMeta>{ pubkey: inputToken.userTokenAddress, isSigner: false, isWritable: true },
+      )
+      extraInstructions.push(extraAccounts.length)
+      extraAccounts.push(
+        <AccountMeta>{ pubkey: inputToken.poolTokenAddress, isSigner: false, isWritable: true },
+      )
+    }
+    for (const inputToken of inputTokens) {
+      for (const extraPriceFeedAddress of inputToken.extraPriceFeedAddresses) {
         extraAccounts.push(
-          <AccountMeta>{ pubkey: inputToken.userTokenAddress, isSigner: false, isWritable: true },
+          <AccountMeta>{ pubkey: extraPriceFeedAddress, isSigner: false, isWritable: false },
         )
       }
-      else {
-        extraInstructions.push(index2)
-      }
-
-      const index3 = extraAccounts.findIndex(meta => meta.pubkey.toBase58() === inputToken.poolTokenAddress.toBase58())
-      if (index3 === -1) {
-        extraInstructions.push(extraAccounts.length)
+    }
+    for (const inputToken of inputTokens) {
+      if (inputToken.twapAddress) {
         extraAccounts.push(
-          <AccountMeta>{ pubkey: inputToken.poolTokenAddress, isSigner: false, isWritable: true },
+          <AccountMeta>{ pubkey: inputToken.twapAddress, isSigner: false, isWritable: true },
         )
       }
-      else {
-        extraInstructions.push(index3)
-      }
     }
 
     const request: MintRequest = {
       amount,
       extraInstructions: Buffer.from(extraInstructions),
+      maxInputAmounts,
     }
 
     const data = coder.instruction.encode('mint', request)
@@ -358,6 +572,7 @@ export class CusdFactoryInstructionService {
     cusdTokenMintAddress: PublicKey,
     userCusdTokenAddress: PublicKey,
     amount: BN,
+    maxCusdIn: BN,
     outputToken: OutputTokenPair,
     chainlinkProgramId: PublicKey,
     cusdFactoryProgramId: PublicKey,
@@ -368,9 +583,25 @@ export class CusdFactoryInstructionService {
       <AccountMeta>{ pubkey: outputToken.poolTokenAddress, isSigner: false, isWritable: true },
       <AccountMeta>{ pubkey: outputToken.userTokenAddress, isSigner: false, isWritable: true },
     ]
+    for (const extraPriceFeedAddress of outputToken.extraPriceFeedAddresses) {
+      extraAccounts.push(
+        <AccountMeta>{ pubkey: extraPriceFeedAddress, isSigner: false, isWritable: false },
+      )
+    }
+    if (outputToken.twapAddress) {
+      extraAccounts.push(
+        <AccountMeta>{ pubkey: outputToken.twapAddress, isSigner: false, isWritable: true },
+      )
+    }
+    if (outputToken.feeTokenAddress) {
+      extraAccounts.push(
+        <AccountMeta>{ pubkey: outputToken.feeTokenAddress, isSigner: false, isWritable: true },
+      )
+    }
 
     const request: BurnRequest = {
       amount,
+      maxCusdIn,
     }
 
     const data = coder.instruction.encode('burn', request)
@@ -406,34 +637,58 @@ export class CusdFactoryInstructionService {
     })
   }
 
-  static withdrawToken(
+  static createAppData(
     rootAddress: PublicKey,
-    poolTokenAddress: PublicKey,
-    recipientTokenAddress: PublicKey,
-    amount: BN,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
-    const request: WithdrawTokenRequest = {
-      amount,
-    }
+    const request: CreateAppDataRequest = { }
 
-    const data = coder.instruction.encode('withdrawToken', request)
+    const data = coder.instruction.encode('createAppData', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
     const [appDataAddress,] = this.findAppDataAddress(
       cusdFactoryProgramId,
     )
-    const [rootSignerAddress,] = this.findRootSignerAddress(
+
+    const keys: AccountMeta[] = [
+      <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: true },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: true },
+      <AccountMeta>{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ]
+
+    return new TransactionInstruction({
+      data,
+      keys,
+      programId: cusdFactoryProgramId,
+    })
+  }
+
+  static createAdminRegistry(
+    rootAddress: PublicKey,
+    signers: PublicKey[],
+    threshold: number,
+    cusdFactoryProgramId: PublicKey,
+  ): TransactionInstruction {
+
+    const request: CreateAdminRegistryRequest = {
+      signers,
+      threshold,
+    }
+
+    const data = coder.instruction.encode('createAdminRegistry', request)
+
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
       cusdFactoryProgramId,
     )
 
     const keys: AccountMeta[] = [
-      <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: false },
-      <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: false },
-      <AccountMeta>{ pubkey: rootSignerAddress, isSigner: false, isWritable: false },
-      <AccountMeta>{ pubkey: poolTokenAddress, isSigner: false, isWritable: true },
-      <AccountMeta>{ pubkey: recipientTokenAddress, isSigner: false, isWritable: true },
-      <AccountMeta>{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: true },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: true },
+      <AccountMeta>{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ]
 
     return new TransactionInstruction({
@@ -443,29 +698,63 @@ export class CusdFactoryInstructionService {
     })
   }
 
-  static unlockTokenMint(
+  static setAppData(
     rootAddress: PublicKey,
-    tokenMintAddress: PublicKey,
+    limit: number,
+    timelockDelay: number,
+    maxPriceAge: number,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
-    const request: UnlockTokenMintRequest = { }
+    const request: SetAppDataRequest = {
+      limit,
+      timelockDelay,
+      maxPriceAge,
+    }
 
-    const data = coder.instruction.encode('unlockTokenMint', request)
+    const data = coder.instruction.encode('setAppData', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
     const [appDataAddress,] = this.findAppDataAddress(
       cusdFactoryProgramId,
     )
-    const [rootSignerAddress,] = this.findRootSignerAddress(
+
+    const keys: AccountMeta[] = [
+      <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: false },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: true },
+    ]
+
+    return new TransactionInstruction({
+      data,
+      keys,
+      programId: cusdFactoryProgramId,
+    })
+  }
+
+  static grantRole(
+    rootAddress: PublicKey,
+    role: Role,
+    memberAddress: PublicKey,
+    cusdFactoryProgramId: PublicKey,
+  ): TransactionInstruction {
+
+    const request: GrantRoleRequest = {
+      role,
+      member: memberAddress,
+    }
+
+    const data = coder.instruction.encode('grantRole', request)
+
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
       cusdFactoryProgramId,
     )
 
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: false },
-      <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: false },
-      <AccountMeta>{ pubkey: rootSignerAddress, isSigner: false, isWritable: false },
-      <AccountMeta>{ pubkey: tokenMintAddress, isSigner: false, isWritable: true },
-      <AccountMeta>{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: true },
     ]
 
     return new TransactionInstruction({
@@ -475,22 +764,36 @@ export class CusdFactoryInstructionService {
     })
   }
 
-  static createAppData(
+  static queueOperation(
     rootAddress: PublicKey,
+    derivationPath: Buffer,
+    operation: Operation,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
-    const request: CreateAppDataRequest = { }
+    const request: QueueOperationRequest = {
+      derivationPath,
+      operation,
+    }
 
-    const data = coder.instruction.encode('createAppData', request)
+    const data = coder.instruction.encode('queueOperation', request)
 
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
     const [appDataAddress,] = this.findAppDataAddress(
       cusdFactoryProgramId,
     )
+    const [timelockOperationAddress,] = this.findTimelockOperationAddress(
+      derivationPath,
+      cusdFactoryProgramId,
+    )
 
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: true },
-      <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: true },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: timelockOperationAddress, isSigner: false, isWritable: true },
       <AccountMeta>{ pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ]
 
@@ -501,25 +804,87 @@ export class CusdFactoryInstructionService {
     })
   }
 
-  static setAppData(
+  static approveOperation(
     rootAddress: PublicKey,
-    limit: number,
+    timelockOperationAddress: PublicKey,
     cusdFactoryProgramId: PublicKey,
   ): TransactionInstruction {
 
-    const request: SetAppDataRequest = {
-      limit,
-     }
+    const request: ApproveOperationRequest = { }
 
-    const data = coder.instruction.encode('setAppData', request)
+    const data = coder.instruction.encode('approveOperation', request)
 
-    const [appDataAddress,] = this.findAppDataAddress(
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
       cusdFactoryProgramId,
     )
 
     const keys: AccountMeta[] = [
       <AccountMeta>{ pubkey: rootAddress, isSigner: true, isWritable: false },
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: timelockOperationAddress, isSigner: false, isWritable: true },
+    ]
+
+    return new TransactionInstruction({
+      data,
+      keys,
+      programId: cusdFactoryProgramId,
+    })
+  }
+
+  static executeOperation(
+    timelockOperationAddress: PublicKey,
+    operation: Operation,
+    cusdFactoryProgramId: PublicKey,
+  ): TransactionInstruction {
+
+    const request: ExecuteOperationRequest = { }
+
+    const data = coder.instruction.encode('executeOperation', request)
+
+    const [adminRegistryAddress,] = this.findAdminRegistryAddress(
+      cusdFactoryProgramId,
+    )
+    const [appDataAddress,] = this.findAppDataAddress(
+      cusdFactoryProgramId,
+    )
+    const [rootSignerAddress,] = this.findRootSignerAddress(
+      cusdFactoryProgramId,
+    )
+
+    // accounts touched by the operation, in the order execute_operation reads them
+    let extraAccounts: AccountMeta[] = []
+    if (operation.withdrawToken) {
+      extraAccounts = [
+        <AccountMeta>{ pubkey: operation.withdrawToken.poolToken, isSigner: false, isWritable: true },
+        <AccountMeta>{ pubkey: operation.withdrawToken.recipientToken, isSigner: false, isWritable: true },
+      ]
+    }
+    else if (operation.unlockTokenMint) {
+      extraAccounts = [
+        <AccountMeta>{ pubkey: operation.unlockTokenMint.tokenMint, isSigner: false, isWritable: true },
+        <AccountMeta>{ pubkey: operation.unlockTokenMint.newAuthority, isSigner: false, isWritable: false },
+      ]
+    }
+    else if (operation.setMinterLimits || operation.setMinter) {
+      const minterAddress = operation.setMinterLimits ? operation.setMinterLimits.minter : operation.setMinter!.minter
+      extraAccounts = [
+        <AccountMeta>{ pubkey: minterAddress, isSigner: false, isWritable: true },
+      ]
+    }
+    else if (operation.setBurnerLimits || operation.setBurner) {
+      const burnerAddress = operation.setBurnerLimits ? operation.setBurnerLimits.burner : operation.setBurner!.burner
+      extraAccounts = [
+        <AccountMeta>{ pubkey: burnerAddress, isSigner: false, isWritable: true },
+      ]
+    }
+
+    const keys: AccountMeta[] = [
+      <AccountMeta>{ pubkey: adminRegistryAddress, isSigner: false, isWritable: true },
       <AccountMeta>{ pubkey: appDataAddress, isSigner: false, isWritable: true },
+      <AccountMeta>{ pubkey: rootSignerAddress, isSigner: false, isWritable: false },
+      <AccountMeta>{ pubkey: timelockOperationAddress, isSigner: false, isWritable: true },
+      <AccountMeta>{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ...extraAccounts
     ]
 
     return new TransactionInstruction({
@@ -535,6 +900,12 @@ export class CusdFactoryInstructionService {
     return coder.accounts.decode('AppData', data)
   }
 
+  static decodeAdminRegistryData(
+    data: Buffer
+  ): AdminRegistry {
+    return coder.accounts.decode('AdminRegistry', data)
+  }
+
   static decodeMinterData(
     data: Buffer
   ): Minter {
@@ -547,6 +918,24 @@ export class CusdFactoryInstructionService {
     return coder.accounts.decode('Burner', data)
   }
 
+  static decodeTimelockOperationData(
+    data: Buffer
+  ): TimelockOperation {
+    return coder.accounts.decode('TimelockOperation', data)
+  }
+
+  static findAdminRegistryAddress(
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [
+        ADMIN_REGISTRY_SEED_1,
+        ADMIN_REGISTRY_SEED_2,
+      ],
+      cusdFactoryProgramId,
+    )
+  }
+
   static findAppDataAddress(
     cusdFactoryProgramId: PublicKey,
   ): [PublicKey, number] {
@@ -571,6 +960,18 @@ export class CusdFactoryInstructionService {
     )
   }
 
+  static findFeeSignerAddress(
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [
+        FEE_SIGNER_SEED_1,
+        FEE_SIGNER_SEED_2,
+      ],
+      cusdFactoryProgramId,
+    )
+  }
+
   static findMinterAddress(
     derivationPath: Buffer,
     cusdFactoryProgramId: PublicKey,
@@ -596,4 +997,30 @@ export class CusdFactoryInstructionService {
       cusdFactoryProgramId,
     )
   }
+
+  static findTimelockOperationAddress(
+    derivationPath: Buffer,
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [
+        TIMELOCK_OPERATION_SEED,
+        derivationPath,
+      ],
+      cusdFactoryProgramId,
+    )
+  }
+
+  static findTwapAddress(
+    priceFeedAddress: PublicKey,
+    cusdFactoryProgramId: PublicKey,
+  ): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [
+        TWAP_SEED,
+        priceFeedAddress.toBuffer(),
+      ],
+      cusdFactoryProgramId,
+    )
+  }
 }
@@ -2,10 +2,23 @@ export {
   CusdFactoryService
 } from './cusd_factory.service';
 export {
+  AcceptedPrice,
+  AdminRegistry,
   AppData,
   Burner,
+  BurnerConfig,
   CusdFactoryInstructionService,
   InputTokenPair,
+  InputTokenParams,
   Minter,
-  OutputTokenPair
+  MinterConfig,
+  Operation,
+  OracleType,
+  OutputTokenPair,
+  OutputTokenParams,
+  PriceConfig,
+  PriceFeed,
+  PriceMode,
+  Role,
+  TimelockOperation
 } from './cusd_factory_instruction.service';
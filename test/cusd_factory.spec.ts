@@ -16,7 +16,12 @@ import {
 } from '@solana/web3.js'
 import { BN } from 'bn.js'
 import { CusdFactoryService } from '../services/cusd_factory.service'
-import { InputTokenParams, OutputTokenParams } from '../services/cusd_factory_instruction.service'
+import {
+  InputTokenParams,
+  Operation,
+  OutputTokenParams,
+  PriceConfig
+} from '../services/cusd_factory_instruction.service'
 
 describe('chainlink_dfeed_local_test', function() {
 
@@ -50,6 +55,40 @@ describe('chainlink_dfeed_local_test', function() {
     PROGRAM_ID,
   )
 
+  // delay of AppData created by the localhost build
+  const TIMELOCK_DELAY = 10
+  const usdcPriceConfig: PriceConfig = {
+    oracleType: { chainlink: {} },
+    minPrice: new BN(0),
+    maxPrice: new BN(0),
+    extraPriceFeeds: [],
+    quorum: 0,
+    maxDivergence: 0,
+    maxDeviation: 0,
+    deviationWindow: 0,
+    pauseOnDeviation: false,
+    maxPegDeviation: 0,
+    maxConfidenceRatio: 0,
+    priceMode: { spot: {} },
+  }
+
+  // queued operations are signed off by ownerAccount, the only signer, and executed once the delay has passed
+  async function approveAndExecuteOperation(timelockOperationAddress: PublicKey) {
+    await CusdFactoryService.approveOperation(
+      connection,
+      ownerAccount,
+      timelockOperationAddress,
+      PROGRAM_ID,
+    )
+    await new Promise(resolve => setTimeout(resolve, (TIMELOCK_DELAY + 5) * 1000))
+    await CusdFactoryService.executeOperation(
+      connection,
+      ownerAccount,
+      timelockOperationAddress,
+      PROGRAM_ID,
+    )
+  }
+
   before(async function() {
     defaultAccount = await SolanaConfigService.getDefaultAccount()
     ownerAccount = await TestAccountService.getAccount(0)
@@ -64,7 +103,7 @@ describe('chainlink_dfeed_local_test', function() {
       connection,
       defaultAccount,
       ownerAccount.publicKey,
-      17000000,
+      1000000000,
     )
     await SystemProgramService.transfer(
       connection,
@@ -72,11 +111,12 @@ describe('chainlink_dfeed_local_test', function() {
       testAccount1.publicKey,
       1000000,
     )
+    // testAccount2 also pays for the fee token account created by its first burn
     await SystemProgramService.transfer(
       connection,
       defaultAccount,
       testAccount2.publicKey,
-      1000000,
+      10000000,
     )
 
     // Create token mint if not exists
@@ -145,6 +185,8 @@ describe('chainlink_dfeed_local_test', function() {
       connection,
       ownerAccount,
       24,
+      TIMELOCK_DELAY,
+      3600,
       PROGRAM_ID,
     )
     // Initialize all token account for Coin98DollarMintBurn
@@ -180,13 +222,30 @@ describe('chainlink_dfeed_local_test', function() {
           priceFeedAddress: usdcPriceFeedAddress,
           decimals: 6,
           percentage: 10000,
+          priceConfig: usdcPriceConfig,
         },
       ],
       30,
-      new BN('1000000000000'),
-      new BN('1000000000'),
+      new BN(0),
+      PROGRAM_ID,
+    )
+  })
+
+  it('raise USDC only minter limits', async function() {
+    const timelockOperationAddress = await CusdFactoryService.queueOperation(
+      connection,
+      ownerAccount,
+      (Math.random() * 1000).toString(),
+      <Operation>{
+        setMinterLimits: {
+          minter: usdcOnlyMinterAddress,
+          totalMintedLimit: new BN('1000000000000'),
+          perPeriodMintedLimit: new BN('1000000000'),
+        },
+      },
       PROGRAM_ID,
     )
+    await approveAndExecuteOperation(timelockOperationAddress)
   })
 
   it('mint 100 CUSD from 100 USDC', async function() {
@@ -216,6 +275,7 @@ describe('chainlink_dfeed_local_test', function() {
       usdcOnlyMinterAddress,
       cusdTokenAccount.publicKey,
       new BN('100000000'),
+      [new BN('100000000')],
       testAccount1CusdTokenAddress,
       CHAINLINK_DFEED_PROGRAM_ID,
       PROGRAM_ID,
@@ -232,14 +292,31 @@ describe('chainlink_dfeed_local_test', function() {
         tokenAddress: usdcTokenAccount.publicKey,
         priceFeedAddress: usdcPriceFeedAddress,
         decimals: 6,
+        priceConfig: usdcPriceConfig,
       },
       30,
-      new BN('1000000000000'),
-      new BN('1000000000'),
+      new BN(0),
       PROGRAM_ID,
     )
   })
 
+  it('raise USDC burner limits', async function() {
+    const timelockOperationAddress = await CusdFactoryService.queueOperation(
+      connection,
+      ownerAccount,
+      (Math.random() * 1000).toString(),
+      <Operation>{
+        setBurnerLimits: {
+          burner: usdcBurnerAddress,
+          totalBurnedLimit: new BN('1000000000000'),
+          perPeriodBurnedLimit: new BN('1000000000'),
+        },
+      },
+      PROGRAM_ID,
+    )
+    await approveAndExecuteOperation(timelockOperationAddress)
+  })
+
   it('burn 50 CUSD for 50 USDC', async function() {
     const testAccount2CusdTokenAddress = await TokenProgramService.createAssociatedTokenAccount(
       connection,
@@ -273,6 +350,7 @@ describe('chainlink_dfeed_local_test', function() {
       usdcOnlyMinterAddress,
       cusdTokenAccount.publicKey,
       new BN('75000000'),
+      [new BN('75000000')],
       testAccount2CusdTokenAddress,
       CHAINLINK_DFEED_PROGRAM_ID,
       PROGRAM_ID,
@@ -284,6 +362,7 @@ describe('chainlink_dfeed_local_test', function() {
       cusdTokenAccount.publicKey,
       testAccount2CusdTokenAddress,
       new BN('50000000'),
+      new BN('50000000'),
       testAccount2UsdcTokenAddress,
       CHAINLINK_DFEED_PROGRAM_ID,
       PROGRAM_ID,
@@ -308,14 +387,16 @@ describe('chainlink_dfeed_local_test', function() {
       new BN('10000000'),
     )
 
-    await CusdFactoryService.withdrawToken(
+    const timelockOperationAddress = await CusdFactoryService.queueWithdrawToken(
       connection,
       ownerAccount,
+      (Math.random() * 1000).toString(),
       poolC98TokenAddress,
       ownerAccount.publicKey,
       new BN('10000000'),
       PROGRAM_ID,
     )
+    await approveAndExecuteOperation(timelockOperationAddress)
   })
 
   it('unlock token mint', async function() {
@@ -332,12 +413,15 @@ describe('chainlink_dfeed_local_test', function() {
       null,
     )
 
-    await CusdFactoryService.unlockTokenMint(
+    const timelockOperationAddress = await CusdFactoryService.queueUnlockTokenMint(
       connection,
       ownerAccount,
+      (Math.random() * 1000).toString(),
       randomTokenMint.publicKey,
+      ownerAccount.publicKey,
       PROGRAM_ID,
     )
+    await approveAndExecuteOperation(timelockOperationAddress)
 
     await TokenProgramService.mint(
       connection,